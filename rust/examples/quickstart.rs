@@ -1,6 +1,7 @@
 //! FRAMEQUERY_API_KEY=fq_live_... cargo run --example quickstart
 
-use framequery::{Client, ClientBuilder, ProcessOptions};
+use framequery::{ClientBuilder, ProcessOptions};
+use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::main]
@@ -56,13 +57,14 @@ async fn main() -> framequery::Result<()> {
     let opts = ProcessOptions {
         poll_interval: Duration::from_secs(3),
         timeout: Duration::from_secs(600),
-        on_progress: Some(Box::new(|job| {
+        on_progress: Some(Arc::new(|job| {
             print!("  Status: {}", job.status);
             if let Some(eta) = job.eta_seconds {
                 print!(" (ETA: {:.0}s)", eta);
             }
             println!();
         })),
+        ..Default::default()
     };
 
     let result = client
@@ -79,7 +81,7 @@ async fn main() -> framequery::Result<()> {
     // -----------------------------------------------------------------------
     // 5. Upload without waiting (fire-and-forget)
     // -----------------------------------------------------------------------
-    let job = client.upload("another_video.mp4").await?;
+    let job = client.upload("another_video.mp4", None).await?;
     println!("Uploaded! Job ID: {} (status: {})", job.id, job.status);
 
     // Check it later:
@@ -1,39 +1,116 @@
-use std::path::Path;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use flate2::write::GzEncoder;
+use futures::future::{select, Either};
+use futures::{Stream, StreamExt};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH,
+    CONTENT_TYPE, ETAG, IF_NONE_MATCH, RETRY_AFTER,
+};
 use serde::de::DeserializeOwned;
 use serde_json::json;
+use tokio::sync::watch;
 use tokio::time::Instant;
 
 use crate::errors::{FrameQueryError, Result};
+use crate::handle::JobHandle;
+use crate::metrics::{endpoint_label, Metrics, NoopMetrics};
 use crate::models::{
-    job_from_value, processing_result_from_value, AudioTrackTranscript, BatchAPIResponse,
-    BatchJobEntry, BatchOptions, BatchResult, CreateJobFromUrlResponse, CreateJobResponse,
-    GetAudioTrackResponse, GetAudioTracksResponse, GetJobResponse, GetQuotaResponse, Job, JobPage,
-    ListJobsResponse, ProcessOptions, ProcessingResult, Quota,
+    artifact_url, job_from_value, processed_data_mismatch, processing_result_from_value,
+    transcript_from_value, ArtifactKind, AudioTrackTranscript, BatchAPIResponse, BatchJobEntry,
+    BatchOptions, BatchResult, CancelSummary, CreateJobData, CreateJobFromUrlResponse,
+    CreateJobResponse, GetAudioTrackResponse, GetAudioTracksResponse, GetJobResponse,
+    GetJobStatusResponse, GetQuotaResponse, Job, JobPage, JobStatus, ListJobsOptions,
+    ListJobsResponse, ProcessEvent, ProcessOptions, ProcessingResult, ProgressUpdate, Quota,
+    RateLimitInfo, TranscriptSegment,
 };
+use crate::shutdown::{Registration, Shutdown};
+use crate::transport::Transport;
 
 const DEFAULT_BASE_URL: &str = "https://api.framequery.com/v1/api";
+const DEFAULT_API_PATH: &str = "/v1/api";
 const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_USER_AGENT: &str = concat!("framequery-rust/", env!("CARGO_PKG_VERSION"));
+/// How many 404s the first poll of a job tolerates before giving up.
+const POLL_NOT_FOUND_RETRIES: u32 = 2;
+/// Chunk size for uploads that report progress.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// How many times `upload` re-creates a job after the signed URL is rejected (401/403).
+const MAX_UPLOAD_URL_REFRESHES: u32 = 2;
+/// Weight of the newest ETA reading in `ProgressUpdate::estimated_completion`.
+const ETA_SMOOTHING: f64 = 0.3;
+
+/// Where the API key goes on each request.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <key>`.
+    #[default]
+    Bearer,
+    /// The raw key in the named header, e.g. `X-Api-Key: <key>`. For API gateways.
+    ApiKeyHeader(String),
+}
 
 /// Configures and builds a [`Client`].
 pub struct ClientBuilder {
     api_key: Option<String>,
+    api_key_file: Option<PathBuf>,
+    api_key_fn: Option<Box<ApiKeyFn>>,
     base_url: String,
     max_retries: u32,
+    retry_on: Vec<u16>,
+    max_retry_elapsed: Option<Duration>,
+    max_backoff: Duration,
     timeout: Duration,
+    control_plane_timeout: Option<Duration>,
+    compression: bool,
+    auth_scheme: AuthScheme,
+    user_agent: Option<String>,
+    upload_timeout: Option<Duration>,
+    pool_max_idle_per_host: usize,
+    pool_idle_timeout: Option<Duration>,
+    http2_prior_knowledge: bool,
+    allow_insecure: bool,
+    metrics: Arc<dyn Metrics>,
+    debug_log: bool,
+    retain_raw: bool,
+    strict_parsing: bool,
 }
 
+type ApiKeyFn = dyn Fn() -> Result<String> + Send + Sync;
+
 impl ClientBuilder {
     /// Defaults: base URL `https://api.framequery.com/v1/api`, 3 retries, 60s timeout.
     pub fn new() -> Self {
         Self {
             api_key: None,
+            api_key_file: None,
+            api_key_fn: None,
             base_url: DEFAULT_BASE_URL.to_string(),
             max_retries: DEFAULT_MAX_RETRIES,
+            retry_on: Vec::new(),
+            max_retry_elapsed: None,
+            max_backoff: DEFAULT_MAX_BACKOFF,
             timeout: DEFAULT_TIMEOUT,
+            control_plane_timeout: None,
+            compression: false,
+            auth_scheme: AuthScheme::Bearer,
+            user_agent: None,
+            upload_timeout: None,
+            pool_max_idle_per_host: usize::MAX,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            http2_prior_knowledge: false,
+            allow_insecure: false,
+            metrics: Arc::new(NoopMetrics),
+            debug_log: false,
+            retain_raw: true,
+            strict_parsing: false,
         }
     }
 
@@ -43,50 +120,278 @@ impl ClientBuilder {
         self
     }
 
-    /// Override the base URL.
+    /// Read the API key from a file at build time, e.g. a mounted secret. Surrounding
+    /// whitespace and trailing newlines are trimmed. Used if no `api_key` was set.
+    pub fn api_key_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.api_key_file = Some(path.into());
+        self
+    }
+
+    /// Fetch the API key from a callback at build time, e.g. a secret-manager lookup.
+    /// Used if neither `api_key` nor `api_key_file` was set. The result is trimmed.
+    pub fn api_key_fn(mut self, f: impl Fn() -> Result<String> + Send + Sync + 'static) -> Self {
+        self.api_key_fn = Some(Box::new(f));
+        self
+    }
+
+    /// Override the base URL. A bare host (`https://api.framequery.com`) gets the default
+    /// `/v1/api` path appended; trailing slashes are dropped.
     pub fn base_url(mut self, url: impl Into<String>) -> Self {
         self.base_url = url.into();
         self
     }
 
-    /// Max retries for 5xx, 429, and network errors. Default: 3.
+    /// Allow a non-`https` base URL, and non-`https` signed upload URLs from the API. Off
+    /// by default so the API key and files aren't sent in cleartext by accident; `http`
+    /// to localhost is always allowed, for testing.
+    pub fn allow_insecure(mut self, allow: bool) -> Self {
+        self.allow_insecure = allow;
+        self
+    }
+
+    /// Print each API request and response to stderr: method, URL, headers (API key
+    /// redacted), body size, status, and a body snippet. For bug reports and local
+    /// debugging. Default: off.
+    pub fn debug_log(mut self, enabled: bool) -> Self {
+        self.debug_log = enabled;
+        self
+    }
+
+    /// Keep the full JSON response in `Job::raw` and `ProcessingResult::raw`. With
+    /// `false`, `raw` is `Value::Null` once the typed fields are filled in, which saves a
+    /// lot of memory when holding thousands of results. `Job::result` reads `raw`, so it
+    /// returns `None` on jobs from such a client; use `wait_for_job` instead. Default:
+    /// `true`.
+    pub fn retain_raw(mut self, retain: bool) -> Self {
+        self.retain_raw = retain;
+        self
+    }
+
+    /// Fail with `Decode` when a completed job's `processedData.scenes` or `.transcript`
+    /// is present but doesn't have the expected shape. By default such fields are read
+    /// as empty, which keeps working through schema changes but can hide data loss.
+    /// Default: `false`.
+    pub fn strict_parsing(mut self, strict: bool) -> Self {
+        self.strict_parsing = strict;
+        self
+    }
+
+    /// Report requests, retries, uploads, and job durations to `metrics`. Default: no-op.
+    pub fn metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Arc::new(metrics);
+        self
+    }
+
+    /// Max retries for 5xx, 429, 408, `retry_on` codes, and network errors. Default: 3.
     pub fn max_retries(mut self, n: u32) -> Self {
         self.max_retries = n;
         self
     }
 
-    /// Per-request HTTP timeout. Default: 60s.
+    /// Also retry these status codes, e.g. a proxy's 425. 5xx, 429, and 408 are always
+    /// retried. Adds to any codes from earlier calls.
+    pub fn retry_on(mut self, codes: &[u16]) -> Self {
+        self.retry_on.extend_from_slice(codes);
+        self
+    }
+
+    /// Stop retrying once the next backoff would push a request's total time past `d`,
+    /// even if attempts remain. Bounds worst-case latency. Default: no cap.
+    pub fn max_retry_elapsed(mut self, d: Duration) -> Self {
+        self.max_retry_elapsed = Some(d);
+        self
+    }
+
+    /// Longest single backoff sleep between retries. Default: 30s.
+    pub fn max_backoff(mut self, d: Duration) -> Self {
+        self.max_backoff = d;
+        self
+    }
+
+    /// Timeout for signed-URL uploads, artifact downloads, and `download_result_to`,
+    /// which can take far longer than API calls. `None` means no timeout. Default: `None`.
+    pub fn upload_timeout(mut self, d: Option<Duration>) -> Self {
+        self.upload_timeout = d;
+        self
+    }
+
+    /// Per-request HTTP timeout for API calls. Default: 60s.
     pub fn timeout(mut self, d: Duration) -> Self {
         self.timeout = d;
         self
     }
 
-    /// Build the [`Client`]. Falls back to `FRAMEQUERY_API_KEY` env var if no key was set.
-    /// Returns `Err(Authentication)` if no key is found.
+    /// Per-request HTTP timeout for `POST` API calls (creating, submitting, and
+    /// cancelling jobs) in place of `timeout`. These are quick, so a short limit fails
+    /// fast on a slow backend and leaves the rest to retries; polls and other `GET`s
+    /// keep `timeout`, and uploads `upload_timeout`. `None` means `timeout`.
+    /// Default: `None`.
+    pub fn control_plane_timeout(mut self, d: Option<Duration>) -> Self {
+        self.control_plane_timeout = d;
+        self
+    }
+
+    /// Max idle connections kept per host. Default: unlimited, as in reqwest.
+    pub fn pool_max_idle_per_host(mut self, n: usize) -> Self {
+        self.pool_max_idle_per_host = n;
+        self
+    }
+
+    /// How long an idle pooled connection is kept open. `None` keeps them indefinitely.
+    /// Default: 90s, as in reqwest.
+    pub fn pool_idle_timeout(mut self, d: Option<Duration>) -> Self {
+        self.pool_idle_timeout = d;
+        self
+    }
+
+    /// Speak HTTP/2 to the API without negotiating it first, for multiplexing many
+    /// concurrent requests over one connection. Signed-URL transfers are unaffected.
+    /// Default: off.
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Gzip JSON request bodies (`Content-Encoding: gzip`). Default: off.
+    /// Gzip response decompression is always enabled regardless of this setting.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// How to send the API key. Default: [`AuthScheme::Bearer`].
+    pub fn auth_scheme(mut self, scheme: AuthScheme) -> Self {
+        self.auth_scheme = scheme;
+        self
+    }
+
+    /// Identify your application in the `User-Agent`, e.g. `"my-app/1.2"`. Appended to
+    /// the default `framequery-rust/<version>`.
+    pub fn user_agent(mut self, ua: impl Into<String>) -> Self {
+        self.user_agent = Some(ua.into());
+        self
+    }
+
+    /// Build the [`Client`]. The key comes from, in order: `api_key`, `api_key_file`,
+    /// `api_key_fn`, then the `FRAMEQUERY_API_KEY` env var.
+    ///
+    /// Returns `Err(Config)` listing every problem found: an unreadable key file, a
+    /// non-`https` base URL (see `allow_insecure`), an invalid auth header name, and a
+    /// missing key alongside any of those. A missing key on its own is
+    /// `Err(Authentication)`. An error from `api_key_fn` is returned as-is.
     pub fn build(self) -> Result<Client> {
-        let api_key = self
-            .api_key
-            .or_else(|| std::env::var("FRAMEQUERY_API_KEY").ok())
-            .ok_or_else(|| FrameQueryError::Authentication {
-                message: "API key is required. Pass it to ClientBuilder::api_key() \
-                          or set the FRAMEQUERY_API_KEY environment variable."
-                    .into(),
-            })?;
-
-        let http = reqwest::Client::builder()
+        let mut errors = Vec::new();
+
+        let api_key = match (self.api_key, &self.api_key_file, &self.api_key_fn) {
+            (Some(key), _, _) => Some(key),
+            (None, Some(path), _) => match std::fs::read_to_string(path) {
+                Ok(contents) => Some(contents.trim().to_string()).filter(|k| !k.is_empty()),
+                Err(e) => {
+                    errors.push(format!(
+                        "failed to read API key file {}: {e}",
+                        path.display()
+                    ));
+                    None
+                }
+            },
+            (None, None, Some(f)) => Some(f()?.trim().to_string()).filter(|k| !k.is_empty()),
+            (None, None, None) => std::env::var("FRAMEQUERY_API_KEY").ok(),
+        };
+
+        if let Err(FrameQueryError::Config { errors: e }) =
+            check_base_url(&self.base_url, self.allow_insecure)
+        {
+            errors.extend(e);
+        }
+
+        if let AuthScheme::ApiKeyHeader(ref name) = self.auth_scheme {
+            if HeaderName::from_bytes(name.as_bytes()).is_err() {
+                errors.push(format!("invalid auth header name: {name:?}"));
+            }
+        }
+
+        let missing_key = "API key is required. Pass it to ClientBuilder::api_key() \
+                           or set the FRAMEQUERY_API_KEY environment variable.";
+        let api_key = match api_key {
+            Some(key) if errors.is_empty() => key,
+            Some(_) => return Err(FrameQueryError::Config { errors }),
+            None if errors.is_empty() => {
+                return Err(FrameQueryError::Authentication {
+                    message: missing_key.into(),
+                })
+            }
+            None => {
+                errors.push(missing_key.into());
+                return Err(FrameQueryError::Config { errors });
+            }
+        };
+
+        let user_agent = match self.user_agent {
+            Some(ref app) => format!("{DEFAULT_USER_AGENT} {app}"),
+            None => DEFAULT_USER_AGENT.to_string(),
+        };
+
+        let mut http = reqwest::Client::builder()
             .timeout(self.timeout)
-            .build()
-            .map_err(FrameQueryError::Http)?;
+            .user_agent(&user_agent)
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout);
+        if self.http2_prior_knowledge {
+            http = http.http2_prior_knowledge();
+        }
+        let http = http.build().map_err(FrameQueryError::Http)?;
+
+        // Separate pool for bulk transfers so they don't inherit the short API timeout.
+        // Blob stores may not speak HTTP/2, so `http2_prior_knowledge` isn't applied here.
+        let mut upload_http = reqwest::Client::builder()
+            .user_agent(&user_agent)
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout);
+        if let Some(t) = self.upload_timeout {
+            upload_http = upload_http.timeout(t);
+        }
+        let upload_http = upload_http.build().map_err(FrameQueryError::Http)?;
 
         Ok(Client {
-            base_url: self.base_url.trim_end_matches('/').to_string(),
+            base_url: normalize_base_url(&self.base_url),
             api_key,
             http,
+            upload_http,
             max_retries: self.max_retries,
+            retry_on: self.retry_on,
+            max_retry_elapsed: self.max_retry_elapsed,
+            max_backoff: self.max_backoff,
+            control_plane_timeout: self.control_plane_timeout,
+            compression: self.compression,
+            auth_scheme: self.auth_scheme,
+            rate_limit: Arc::default(),
+            last_headers: Arc::default(),
+            transport: None,
+            deadline: None,
+            metrics: self.metrics,
+            debug_log: self.debug_log,
+            retain_raw: self.retain_raw,
+            strict_parsing: self.strict_parsing,
+            allow_insecure: self.allow_insecure,
+            shutdown: Arc::default(),
         })
     }
 }
 
+/// Redacts the API key.
+impl std::fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("api_key", &self.api_key.as_deref().map(redact_key))
+            .field("api_key_file", &self.api_key_file)
+            .field("base_url", &self.base_url)
+            .field("max_retries", &self.max_retries)
+            .field("timeout", &self.timeout)
+            .field("auth_scheme", &self.auth_scheme)
+            .finish_non_exhaustive()
+    }
+}
+
 impl Default for ClientBuilder {
     fn default() -> Self {
         Self::new()
@@ -94,65 +399,171 @@ impl Default for ClientBuilder {
 }
 
 /// FrameQuery API client. See [`ClientBuilder`] for non-default config.
+///
+/// Cloning is cheap: clones share the underlying connection pool, so share one client
+/// across tasks by cloning rather than wrapping it in an `Arc`.
+#[derive(Clone)]
 pub struct Client {
     base_url: String,
     api_key: String,
     http: reqwest::Client,
+    /// Signed-URL uploads and artifact downloads; has `upload_timeout`.
+    upload_http: reqwest::Client,
     max_retries: u32,
+    /// Status codes retried on top of the defaults.
+    retry_on: Vec<u16>,
+    max_retry_elapsed: Option<Duration>,
+    max_backoff: Duration,
+    /// Replaces the client-wide timeout for `POST`s when set.
+    control_plane_timeout: Option<Duration>,
+    compression: bool,
+    auth_scheme: AuthScheme,
+    /// Shared across clones.
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    /// Headers of the latest API response. Shared across clones.
+    last_headers: Arc<Mutex<Option<HeaderMap>>>,
+    /// Replaces HTTP for JSON API calls when set.
+    transport: Option<Arc<dyn Transport>>,
+    /// Set by `with_deadline`.
+    deadline: Option<Instant>,
+    metrics: Arc<dyn Metrics>,
+    /// Set by `ClientBuilder::debug_log`.
+    debug_log: bool,
+    /// Set by `ClientBuilder::retain_raw`.
+    retain_raw: bool,
+    /// Set by `ClientBuilder::strict_parsing`.
+    strict_parsing: bool,
+    /// Set by `ClientBuilder::allow_insecure`.
+    allow_insecure: bool,
+    /// Shared across clones.
+    shutdown: Arc<Shutdown>,
 }
 
 impl Client {
     /// Create a client with default settings. Panics if the HTTP client can't be built (shouldn't happen).
     pub fn new(api_key: impl Into<String>) -> Self {
-        let api_key = api_key.into();
-        let http = reqwest::Client::builder()
-            .timeout(DEFAULT_TIMEOUT)
+        ClientBuilder::new()
+            .api_key(api_key)
             .build()
-            .expect("failed to build HTTP client");
+            .expect("failed to build HTTP client")
+    }
+
+    /// A client whose JSON API calls go to `transport` instead of the network. For
+    /// testing code that uses the SDK; see [`Transport`]. The byte transfers in `upload`
+    /// and `download_artifact` still use HTTP.
+    pub fn with_transport(transport: impl Transport + 'static) -> Self {
+        let mut client = Self::new("fq_test_transport");
+        client.transport = Some(Arc::new(transport));
+        client
+    }
 
+    /// A clone of this client whose calls all stop at `deadline`, e.g. one propagated from
+    /// an upstream request. Requests (body reads included), retries, polling, and
+    /// signed-URL transfers fail with `Timeout` once it passes. Unlike `timeout` options,
+    /// the deadline is shared by every call made through the clone. Accepts
+    /// `std::time::Instant` or `tokio::time::Instant`.
+    pub fn with_deadline(&self, deadline: impl Into<Instant>) -> Self {
         Self {
-            base_url: DEFAULT_BASE_URL.to_string(),
-            api_key,
-            http,
-            max_retries: DEFAULT_MAX_RETRIES,
+            deadline: Some(deadline.into()),
+            ..self.clone()
         }
     }
 
+    /// A clone of this client with a different retry count, for varying retry aggressiveness
+    /// per call: `client.with_retries(0).get_job(id)`. Shares the connection pool.
+    pub fn with_retries(&self, max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..self.clone()
+        }
+    }
+
+    /// Create a client with default settings and the key from `FRAMEQUERY_API_KEY`.
+    ///
+    /// Errors: `Authentication` if the variable is unset.
+    pub fn from_env() -> Result<Self> {
+        ClientBuilder::new().build()
+    }
+
     /// Upload a file and poll until done. Wraps [`upload`](Self::upload) + polling.
     ///
-    /// Errors: `Io` (can't read file), `Timeout` (poll exceeded), `JobFailed`.
+    /// Errors: `Io` (can't read file), `Timeout` (poll or total budget exceeded), `JobFailed`.
     pub async fn process(
         &self,
         path: impl AsRef<Path>,
         opts: Option<ProcessOptions>,
     ) -> Result<ProcessingResult> {
-        let job = self.upload(path, opts.as_ref()).await?;
-        let opts = opts.unwrap_or_default();
-        self.poll(&job.id, &opts).await
+        let total_timeout = opts.as_ref().and_then(|o| o.total_timeout);
+        with_total_timeout(total_timeout, async {
+            let job = self.upload(path, opts.as_ref()).await?;
+            let opts = opts.unwrap_or_default();
+            self.job_created(&opts, &job.id);
+            self.wait_for_job(&job.id, &opts).await
+        })
+        .await
+    }
+
+    /// [`process`](Self::process) with default options and a poll `timeout`, for when
+    /// that's all you'd set.
+    pub async fn process_timeout(
+        &self,
+        path: impl AsRef<Path>,
+        timeout: Duration,
+    ) -> Result<ProcessingResult> {
+        let opts = ProcessOptions {
+            timeout,
+            ..Default::default()
+        };
+        self.process(path, Some(opts)).await
     }
 
     /// Submit a URL for server-side download, poll until done. No local upload.
     ///
-    /// Errors: `Timeout`, `JobFailed`.
+    /// Errors: `Timeout` (poll or total budget exceeded), `JobFailed`.
     pub async fn process_url(
         &self,
         url: &str,
         opts: Option<ProcessOptions>,
     ) -> Result<ProcessingResult> {
-        // Derive a filename from the URL path, or fall back to "video.mp4".
-        let file_name = url
-            .rsplit('/')
-            .next()
-            .filter(|s| !s.is_empty() && s.contains('.'))
-            .unwrap_or("video.mp4");
+        let total_timeout = opts.as_ref().and_then(|o| o.total_timeout);
+        with_total_timeout(total_timeout, self.process_url_inner(url, opts)).await
+    }
+
+    async fn process_url_inner(
+        &self,
+        url: &str,
+        opts: Option<ProcessOptions>,
+    ) -> Result<ProcessingResult> {
+        let opts = opts.unwrap_or_default();
+        let mut resubmits = 0;
+        loop {
+            let job = self.create_url_job(url, Some(&opts)).await?;
+            self.job_created(&opts, &job.id);
+            match self.wait_for_job(&job.id, &opts).await {
+                Err(ref e) if is_source_fetch_failure(e) && resubmits < opts.retry_source_fetch => {
+                    resubmits += 1;
+                    tokio::time::sleep(backoff_for(resubmits, self.max_backoff)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// `POST /jobs/from-url`, returning the new job.
+    async fn create_url_job(&self, url: &str, opts: Option<&ProcessOptions>) -> Result<Job> {
+        // Use the caller's filename, else derive one from the URL path, else "video.mp4".
+        let file_name = opts
+            .and_then(|o| o.file_name.as_deref())
+            .unwrap_or_else(|| file_name_from_url(url));
 
         let mut body = json!({
             "url": url,
             "fileName": file_name,
         });
 
-        if let Some(ref o) = opts {
+        if let Some(o) = opts {
             if let Some(ref cb) = o.callback_url {
+                check_callback_url(cb)?;
                 body["callbackUrl"] = json!(cb);
             }
             if let Some(ref mode) = o.processing_mode {
@@ -164,153 +575,980 @@ impl Client {
             if let Some(ref tracks) = o.audio_tracks {
                 body["audioTracks"] = json!(tracks);
             }
+            if let Some(ref headers) = o.source_headers {
+                body["sourceHeaders"] = json!(headers);
+            }
+            if !o.tags.is_empty() {
+                body["tags"] = json!(o.tags);
+            }
+            merge_extra_fields(&mut body, o.extra_fields.as_ref())?;
         }
 
         let resp: CreateJobFromUrlResponse =
             self.request("POST", "/jobs/from-url", Some(body)).await?;
+        let mut job = job_from_value(resp.data);
+        if job.id.is_empty() {
+            return Err(FrameQueryError::Decode {
+                message: "POST /jobs/from-url response has no jobId".into(),
+                body_snippet: snippet(&job.raw.to_string()),
+            });
+        }
+        if job.filename.is_empty() {
+            job.filename = file_name.to_string();
+        }
+        Ok(self.finish_job(job))
+    }
+
+    /// Upload a file and return just the job id, without polling. For webhook-driven
+    /// flows; set `opts.callback_url` to be notified on completion.
+    pub async fn submit(
+        &self,
+        path: impl AsRef<Path>,
+        opts: Option<&ProcessOptions>,
+    ) -> Result<String> {
+        Ok(self.upload(path, opts).await?.id)
+    }
+
+    /// [`upload`](Self::upload) with a webhook: the backend calls `callback_url` when the
+    /// job finishes, so there's no need to poll. `callback_url` must be `https`.
+    pub async fn upload_with_callback(
+        &self,
+        path: impl AsRef<Path>,
+        callback_url: &str,
+    ) -> Result<Job> {
+        let opts = ProcessOptions {
+            callback_url: Some(callback_url.to_string()),
+            ..Default::default()
+        };
+        self.upload(path, Some(&opts)).await
+    }
+
+    /// Upload a file and return a [`JobHandle`] that cancels the remote job if dropped
+    /// before it completes. Keeps aborted requests from leaving billable jobs running.
+    pub async fn start(
+        &self,
+        path: impl AsRef<Path>,
+        opts: Option<&ProcessOptions>,
+    ) -> Result<JobHandle> {
+        let job = self.upload(path, opts).await?;
+        Ok(JobHandle::new(self.clone(), job.id))
+    }
+
+    /// Submit a URL for server-side download and return just the job id, without
+    /// polling. Counterpart of [`submit`](Self::submit).
+    pub async fn submit_url(&self, url: &str, opts: Option<&ProcessOptions>) -> Result<String> {
+        Ok(self.create_url_job(url, opts).await?.id)
+    }
+
+    /// Submit a URL for server-side download and return the created `Job` immediately,
+    /// without polling. The URL counterpart of [`upload`](Self::upload); `status` and
+    /// `raw` are whatever the API reported at creation.
+    pub async fn submit_url_job(&self, url: &str, opts: Option<&ProcessOptions>) -> Result<Job> {
+        self.create_url_job(url, opts).await
+    }
+
+    /// Run [`process_url`](Self::process_url) over many URLs, at most `concurrency` at a
+    /// time. One URL failing doesn't affect the rest. Results are in input order.
+    /// `opts.file_name` is ignored; each job's filename is derived from its own URL.
+    pub async fn process_urls(
+        &self,
+        urls: Vec<String>,
+        concurrency: usize,
+        opts: Option<ProcessOptions>,
+    ) -> Vec<(String, Result<ProcessingResult>)> {
+        let opts = opts.map(|o| ProcessOptions {
+            file_name: None,
+            ..o
+        });
+        futures::stream::iter(urls)
+            .map(|url| {
+                let opts = opts.clone();
+                async move {
+                    let result = self.process_url(&url, opts).await;
+                    (url, result)
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Like [`process`](Self::process), but reports progress as a stream of
+    /// [`ProcessEvent`]s instead of via callbacks. Uses the job-creation and polling
+    /// settings from `opts`, including `total_timeout`, counted from the first poll of
+    /// the stream; `on_progress` and `until` are ignored.
+    pub fn process_with_events(
+        &self,
+        path: impl AsRef<Path>,
+        opts: Option<ProcessOptions>,
+    ) -> impl Stream<Item = ProcessEvent> + '_ {
+        let path = path.as_ref().to_path_buf();
         let opts = opts.unwrap_or_default();
-        self.poll(&resp.data.job_id, &opts).await
+
+        futures::stream::unfold(
+            (EventPhase::Start, path, opts, None),
+            move |(phase, path, opts, mut budget)| async move {
+                let (event, next) = match phase {
+                    EventPhase::Start => match tokio::fs::metadata(&path).await {
+                        Ok(meta) => {
+                            budget = opts.total_timeout.map(|t| (Instant::now() + t, t));
+                            let (sender, progress) = watch::channel(0);
+                            let (path, opts) = (path.clone(), opts.clone());
+                            let upload = Box::pin(within_budget(budget, async move {
+                                let file_name = upload_file_name(&path);
+                                self.upload_reporting(&path, &file_name, Some(&opts), Some(&sender))
+                                    .await
+                            }));
+                            (
+                                ProcessEvent::UploadProgress {
+                                    sent: 0,
+                                    total: meta.len(),
+                                },
+                                EventPhase::Uploading {
+                                    total: meta.len(),
+                                    upload,
+                                    progress,
+                                },
+                            )
+                        }
+                        Err(e) => failed(FrameQueryError::Io(e)),
+                    },
+                    EventPhase::Uploading {
+                        total,
+                        mut upload,
+                        mut progress,
+                    } => {
+                        // Bytes sent so far (`Left`) or the upload's outcome (`Right`).
+                        // `changed` only fails once the upload has dropped its sender. The
+                        // final `sent == total` is left to the completion event.
+                        let reported = loop {
+                            let changed =
+                                match select(upload.as_mut(), pin!(progress.changed())).await {
+                                    Either::Left((result, _)) => break Either::Right(result),
+                                    Either::Right((changed, _)) => changed.is_ok(),
+                                };
+                            if !changed {
+                                break Either::Right(upload.as_mut().await);
+                            }
+                            let sent = *progress.borrow_and_update();
+                            if sent < total {
+                                break Either::Left(sent);
+                            }
+                        };
+                        match reported {
+                            Either::Left(sent) => (
+                                ProcessEvent::UploadProgress { sent, total },
+                                EventPhase::Uploading {
+                                    total,
+                                    upload,
+                                    progress,
+                                },
+                            ),
+                            Either::Right(Ok(job)) => match self.shutdown.register(&[&job.id]) {
+                                Some(registration) => {
+                                    let (deadline, limit) = self.poll_deadline(opts.timeout);
+                                    (
+                                        ProcessEvent::UploadProgress { sent: total, total },
+                                        EventPhase::Polling {
+                                            job_id: job.id,
+                                            deadline,
+                                            limit,
+                                            polls: 0,
+                                            cache: None,
+                                            started: Instant::now(),
+                                            registration,
+                                        },
+                                    )
+                                }
+                                None => failed(FrameQueryError::Cancelled { job_id: job.id }),
+                            },
+                            Either::Right(Err(e)) => failed(e),
+                        }
+                    }
+                    EventPhase::Polling {
+                        job_id,
+                        deadline,
+                        limit,
+                        mut polls,
+                        mut cache,
+                        started,
+                        registration,
+                    } => {
+                        let step = async {
+                            if polls == 0 {
+                                self.first_poll(&job_id, &opts, deadline, &mut cache, &mut polls)
+                                    .await
+                            } else {
+                                let delay = poll_delay(opts.poll_interval, deadline);
+                                tokio::time::sleep(delay).await;
+                                self.poll_job(&job_id, opts.light_polling, &mut cache).await
+                            }
+                        };
+                        let result = self
+                            .shutdown
+                            .until_triggered(&job_id, within_budget(budget, step))
+                            .await;
+                        let polls = polls + 1;
+                        match result {
+                            Ok(job) => {
+                                if job.is_terminal() {
+                                    self.metrics.on_job_finished(&job.status, started.elapsed());
+                                }
+                                let next = if job.is_failed() {
+                                    EventPhase::Emit(Box::new(ProcessEvent::Failed(
+                                        FrameQueryError::job_failed(&job).to_string(),
+                                    )))
+                                } else if job.is_complete() {
+                                    EventPhase::Emit(Box::new(
+                                        match self.result_from_value(job.raw.clone()) {
+                                            Ok(result) => ProcessEvent::Completed(result),
+                                            Err(e) => ProcessEvent::Failed(e.to_string()),
+                                        },
+                                    ))
+                                } else if let Some(err) =
+                                    poll_limit_error(&opts, polls, deadline, limit)
+                                {
+                                    EventPhase::Emit(Box::new(ProcessEvent::Failed(
+                                        err.to_string(),
+                                    )))
+                                } else {
+                                    EventPhase::Polling {
+                                        job_id,
+                                        deadline,
+                                        limit,
+                                        polls,
+                                        cache,
+                                        started,
+                                        registration,
+                                    }
+                                };
+                                (ProcessEvent::JobStatus(self.finish_job(job)), next)
+                            }
+                            Err(e) => failed(e),
+                        }
+                    }
+                    EventPhase::Emit(event) => (*event, EventPhase::Done),
+                    EventPhase::Done => return None,
+                };
+                Some((event, (next, path, opts, budget)))
+            },
+        )
+    }
+
+    /// Upload a file, then yield transcript segments as polling finds them, for
+    /// live-captioning-style use. Each poll reads whatever partial transcript the job
+    /// has so far and yields the segments ending after the last one yielded. Ends after
+    /// the job completes; yields a final `Err` if it fails, times out, or upload fails,
+    /// or `Decode` under `strict_parsing` if a poll's transcript is malformed.
+    ///
+    /// Polls the full job every time (`light_polling` is ignored, as the status-only
+    /// view has no transcript); `on_progress` and `until` are ignored too.
+    /// `total_timeout` counts from the first poll of the stream.
+    pub fn process_streaming_transcript(
+        &self,
+        path: impl AsRef<Path>,
+        opts: Option<ProcessOptions>,
+    ) -> impl Stream<Item = Result<TranscriptSegment>> + '_ {
+        let path = path.as_ref().to_path_buf();
+        let opts = ProcessOptions {
+            light_polling: false,
+            ..opts.unwrap_or_default()
+        };
+
+        futures::stream::unfold(
+            (TranscriptPhase::Start, path, opts, None),
+            move |(phase, path, opts, mut budget)| async move {
+                let (items, next) = match phase {
+                    TranscriptPhase::Start => {
+                        budget = opts.total_timeout.map(|t| (Instant::now() + t, t));
+                        let uploaded = within_budget(budget, self.upload(&path, Some(&opts))).await;
+                        match uploaded {
+                            Ok(job) => match self.shutdown.register(&[&job.id]) {
+                                Some(registration) => {
+                                    let (deadline, limit) = self.poll_deadline(opts.timeout);
+                                    (
+                                        Vec::new(),
+                                        TranscriptPhase::Polling {
+                                            job_id: job.id,
+                                            deadline,
+                                            limit,
+                                            polls: 0,
+                                            last_end: f64::NEG_INFINITY,
+                                            cache: None,
+                                            registration,
+                                        },
+                                    )
+                                }
+                                None => (
+                                    vec![Err(FrameQueryError::Cancelled { job_id: job.id })],
+                                    TranscriptPhase::Done,
+                                ),
+                            },
+                            Err(e) => (vec![Err(e)], TranscriptPhase::Done),
+                        }
+                    }
+                    TranscriptPhase::Polling {
+                        job_id,
+                        deadline,
+                        limit,
+                        mut polls,
+                        mut last_end,
+                        mut cache,
+                        registration,
+                    } => {
+                        let step = async {
+                            if polls == 0 {
+                                self.first_poll(&job_id, &opts, deadline, &mut cache, &mut polls)
+                                    .await
+                            } else {
+                                let delay = poll_delay(opts.poll_interval, deadline);
+                                tokio::time::sleep(delay).await;
+                                self.poll_job(&job_id, false, &mut cache).await
+                            }
+                        };
+                        let result = self
+                            .shutdown
+                            .until_triggered(&job_id, within_budget(budget, step))
+                            .await
+                            .and_then(|job| {
+                                self.check_processed_data(&job.raw)?;
+                                Ok(job)
+                            });
+                        let polls = polls + 1;
+                        match result {
+                            Ok(job) => {
+                                let fresh: Vec<TranscriptSegment> = transcript_from_value(&job.raw)
+                                    .into_iter()
+                                    .filter(|s| s.end_time > last_end)
+                                    .collect();
+                                last_end = fresh.iter().fold(last_end, |m, s| m.max(s.end_time));
+                                let mut items: Vec<Result<TranscriptSegment>> =
+                                    fresh.into_iter().map(Ok).collect();
+                                let next = if job.is_failed() {
+                                    items.push(Err(FrameQueryError::job_failed(&job)));
+                                    TranscriptPhase::Done
+                                } else if job.is_complete() {
+                                    TranscriptPhase::Done
+                                } else if let Some(err) =
+                                    poll_limit_error(&opts, polls, deadline, limit)
+                                {
+                                    items.push(Err(err));
+                                    TranscriptPhase::Done
+                                } else {
+                                    TranscriptPhase::Polling {
+                                        job_id,
+                                        deadline,
+                                        limit,
+                                        polls,
+                                        last_end,
+                                        cache,
+                                        registration,
+                                    }
+                                };
+                                (items, next)
+                            }
+                            Err(e) => (vec![Err(e)], TranscriptPhase::Done),
+                        }
+                    }
+                    TranscriptPhase::Done => return None,
+                };
+                Some((futures::stream::iter(items), (next, path, opts, budget)))
+            },
+        )
+        .flatten()
     }
 
     /// Upload a file and return immediately. Does `POST /jobs` then `PUT`s the bytes
-    /// to the signed URL. The returned `Job` will be in `PENDING_UPLOAD`.
-    pub async fn upload(&self, path: impl AsRef<Path>, opts: Option<&ProcessOptions>) -> Result<Job> {
+    /// to the signed URL (or `POST`s a multipart form, if the response's `uploadMethod`
+    /// says so). The returned `Job` will be in `PENDING_UPLOAD`.
+    ///
+    /// A failed upload is retried like API calls on 5xx and network errors. If the signed
+    /// URL is rejected (401/403), the job is created again for a fresh URL, up to twice,
+    /// and the job it replaces is cancelled. `opts.idempotency_key` is sent with every
+    /// creation. If the upload fails for good, the job is cancelled so it doesn't sit in
+    /// `PENDING_UPLOAD`, unless `idempotency_key` is set: then it's kept, so retrying
+    /// with the same key gets it back.
+    pub async fn upload(
+        &self,
+        path: impl AsRef<Path>,
+        opts: Option<&ProcessOptions>,
+    ) -> Result<Job> {
         let path = path.as_ref();
+        self.upload_as(path, &upload_file_name(path), opts).await
+    }
 
-        // Validate the file exists and read it into memory.
-        let file_name = path
-            .file_name()
-            .map(|n| n.to_string_lossy().into_owned())
-            .unwrap_or_else(|| "video.mp4".to_string());
+    /// Like [`upload`](Self::upload), but records `file_name` server-side instead of the
+    /// path's basename. For content-addressed storage, where files on disk have hashed
+    /// names but the original name should show up in the job.
+    pub async fn upload_as(
+        &self,
+        path: impl AsRef<Path>,
+        file_name: &str,
+        opts: Option<&ProcessOptions>,
+    ) -> Result<Job> {
+        self.upload_reporting(path.as_ref(), file_name, opts, None)
+            .await
+    }
+
+    /// [`upload_as`](Self::upload_as), also sending the bytes handed to the signed-URL
+    /// upload so far to `progress`. A retried upload starts counting again from 0.
+    async fn upload_reporting(
+        &self,
+        path: &Path,
+        file_name: &str,
+        opts: Option<&ProcessOptions>,
+        progress: Option<&watch::Sender<u64>>,
+    ) -> Result<Job> {
+        let file_name = file_name.to_string();
 
-        let file_bytes = tokio::fs::read(path).await.map_err(FrameQueryError::Io)?;
+        // Validate the file exists and read it into memory.
+        let file_bytes =
+            bytes::Bytes::from(tokio::fs::read(path).await.map_err(FrameQueryError::Io)?);
+        if !opts.is_some_and(|o| o.skip_media_check)
+            && !infer::is_video(&file_bytes)
+            && !infer::is_audio(&file_bytes)
+        {
+            return Err(FrameQueryError::UnsupportedMedia {
+                file_name,
+                detected: infer::get(&file_bytes).map(|t| t.mime_type().to_string()),
+            });
+        }
 
         // Step 1: Create the job.
-        let mut body = json!({ "fileName": file_name });
-        if let Some(o) = opts {
-            if let Some(ref url) = o.callback_url {
-                body["callbackUrl"] = json!(url);
-            }
-            if let Some(ref mode) = o.processing_mode {
-                body["processingMode"] = json!(mode);
+        let body = create_job_body(&file_name, opts)?;
+
+        // Step 2: Upload file to signed URL. A 401/403 means the signed URL expired or was
+        // rejected, so retrying it is pointless: create the job again for a fresh URL.
+        let mut refreshes = 0;
+        let mut replaced: Option<String> = None;
+        let (job_id, checksum) = loop {
+            let resp: CreateJobResponse = self
+                .request("POST", "/jobs", Some(body.clone()))
+                .await
+                .map_err(|e| e.with_file_size(file_bytes.len() as u64))?;
+            // An idempotency key can hand back the same job; only a different one is stale.
+            if let Some(old) = replaced.take().filter(|old| *old != resp.data.job_id) {
+                let _ = self.cancel_job(&old).await;
             }
-            if let Some(ref key) = o.idempotency_key {
-                body["idempotencyKey"] = json!(key);
-            }
-            if let Some(ref tracks) = o.audio_tracks {
-                body["audioTracks"] = json!(tracks);
+            let checksum = resp
+                .data
+                .checksum_algorithm
+                .as_deref()
+                .and_then(|algorithm| upload_checksum(algorithm, &file_bytes));
+            match self
+                .upload_to_signed_url(
+                    &resp.data,
+                    &file_name,
+                    &file_bytes,
+                    checksum.as_ref(),
+                    progress,
+                )
+                .await
+            {
+                Ok(()) => break (resp.data.job_id, checksum),
+                Err(FrameQueryError::Api {
+                    status_code: 401 | 403,
+                    ..
+                }) if refreshes < MAX_UPLOAD_URL_REFRESHES => {
+                    refreshes += 1;
+                    replaced = Some(resp.data.job_id);
+                }
+                Err(e) => {
+                    self.abandon_job(&resp.data.job_id, opts).await;
+                    return Err(e);
+                }
             }
-        }
-        let resp: CreateJobResponse = self.request("POST", "/jobs", Some(body)).await?;
+        };
+
+        let mut job = uploaded_job(job_id, file_name, opts);
+        job.upload_checksum = checksum.map(|(_, digest)| digest);
+        Ok(self.finish_job(job))
+    }
 
-        // Step 2: Upload file to signed URL.
-        let upload_resp = self
-            .http
-            .put(&resp.data.upload_url)
-            .header(CONTENT_TYPE, "application/octet-stream")
-            .body(file_bytes)
-            .send()
+    /// Upload `len` bytes from `stream` as `file_name`, for sources that aren't a local
+    /// file or that the caller wants to instrument (progress, rate limiting, bandwidth
+    /// shaping) by wrapping the stream. The stream must yield exactly `len` bytes, which
+    /// are sent as they arrive rather than buffered.
+    ///
+    /// A stream can only be read once, so unlike [`upload`](Self::upload) a failed upload
+    /// isn't retried, a rejected signed URL isn't refreshed, no checksum is sent even if
+    /// the API asks for one, and there's no media check. A job whose upload fails is
+    /// cancelled as in `upload`.
+    ///
+    /// Errors: `Api` / `Http` if the upload fails, including when the stream yields an
+    /// error.
+    pub async fn upload_stream<S, E>(
+        &self,
+        file_name: &str,
+        stream: S,
+        len: u64,
+        opts: Option<&ProcessOptions>,
+    ) -> Result<Job>
+    where
+        S: futures::Stream<Item = std::result::Result<bytes::Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        let body = create_job_body(file_name, opts)?;
+        let resp: CreateJobResponse = self
+            .request("POST", "/jobs", Some(body))
             .await
-            .map_err(FrameQueryError::Http)?;
-
-        if !upload_resp.status().is_success() {
-            let status = upload_resp.status().as_u16();
-            let text = upload_resp.text().await.unwrap_or_default();
-            return Err(FrameQueryError::Api {
-                status_code: status,
-                message: format!("upload to signed URL failed: {text}"),
-                body: None,
-            });
+            .map_err(|e| e.with_file_size(len))?;
+        let target = resp.data;
+
+        let sent = self
+            .until_deadline(async {
+                check_upload_url(&target, self.allow_insecure)?;
+                let req = self.signed_upload_request(
+                    &target,
+                    file_name,
+                    reqwest::Body::wrap_stream(stream),
+                    len,
+                    None,
+                );
+                self.signed_upload_outcome(req.send().await, len).await
+            })
+            .await;
+        if let Err(e) = sent {
+            self.abandon_job(&target.job_id, opts).await;
+            return Err(e);
         }
-
-        // Return a Job struct representing the freshly created job.
-        Ok(Job {
-            id: resp.data.job_id.clone(),
-            status: "PENDING_UPLOAD".to_string(),
-            filename: file_name,
-            created_at: String::new(),
-            eta_seconds: None,
-            audio_track_count: None,
-            audio_tracks_completed: None,
-            audio_track_names: Vec::new(),
-            raw: json!({
-                "jobId": resp.data.job_id,
-                "status": "PENDING_UPLOAD",
-            }),
-        })
+        Ok(self.finish_job(uploaded_job(target.job_id, file_name.to_string(), opts)))
     }
 
     /// `GET /jobs/{job_id}`.
     pub async fn get_job(&self, job_id: &str) -> Result<Job> {
+        Ok(self.finish_job(self.fetch_job(job_id).await?))
+    }
+
+    /// [`Job::result`], but honoring this client's settings: `Decode` under
+    /// `strict_parsing` if `processedData` is malformed, and no `raw` unless
+    /// `retain_raw` is on. `Ok(None)` if the job isn't complete or has no processed data.
+    pub fn job_result(&self, job: &Job) -> Result<Option<ProcessingResult>> {
+        if !job.is_complete() || job.raw.get("processedData").is_none() {
+            return Ok(None);
+        }
+        self.result_from_value(job.raw.clone()).map(Some)
+    }
+
+    /// `GET /jobs/{job_id}`, keeping `raw` regardless of `retain_raw`, for internal use.
+    async fn fetch_job(&self, job_id: &str) -> Result<Job> {
         let resp: GetJobResponse = self
             .request("GET", &format!("/jobs/{job_id}"), None)
             .await?;
         Ok(job_from_value(resp.data))
     }
 
-    /// `GET /jobs` with optional `limit`, `cursor`, and `status` filter.
-    pub async fn list_jobs(
-        &self,
-        limit: Option<u32>,
-        cursor: Option<&str>,
-        status: Option<&str>,
-    ) -> Result<JobPage> {
-        let mut query_parts: Vec<String> = Vec::new();
-
-        if let Some(l) = limit {
-            query_parts.push(format!("limit={l}"));
-        }
-        if let Some(c) = cursor {
-            query_parts.push(format!("cursor={c}"));
-        }
-        if let Some(s) = status {
-            query_parts.push(format!("status={s}"));
-        }
-
-        let path = if query_parts.is_empty() {
-            "/jobs".to_string()
-        } else {
-            format!("/jobs?{}", query_parts.join("&"))
-        };
-
-        let resp: ListJobsResponse = self.request("GET", &path, None).await?;
-
-        let jobs = resp.data.into_iter().map(job_from_value).collect();
-
-        Ok(JobPage {
-            jobs,
-            next_cursor: resp.next_cursor,
-        })
+    /// Re-fetch `job` by id and overwrite it in place, `raw` included (subject to
+    /// `ClientBuilder::retain_raw`). For UIs that hold a `Job` and update it periodically.
+    /// On error, `job` is left unchanged.
+    pub async fn refresh(&self, job: &mut Job) -> Result<()> {
+        *job = self.get_job(&job.id).await?;
+        Ok(())
     }
 
-    /// `GET /quota`.
-    pub async fn get_quota(&self) -> Result<Quota> {
-        let resp: GetQuotaResponse = self.request("GET", "/quota", None).await?;
+    /// `GET /jobs/{job_id}`, reading only the status. Skips building a full [`Job`] and
+    /// its `raw` copy; use it for cheap "is it done yet?" checks.
+    pub async fn get_status(&self, job_id: &str) -> Result<JobStatus> {
+        let resp: GetJobStatusResponse = self
+            .request("GET", &format!("/jobs/{job_id}"), None)
+            .await?;
         Ok(resp.data)
     }
 
-    /// `GET /jobs/{job_id}/audio-tracks` -- retrieve all audio track transcripts.
-    pub async fn get_audio_tracks(&self, job_id: &str) -> Result<Vec<AudioTrackTranscript>> {
-        let resp: GetAudioTracksResponse = self
-            .request("GET", &format!("/jobs/{job_id}/audio-tracks"), None)
+    /// `POST /jobs/{job_id}/cancel` -- stop a job that hasn't finished, so it isn't billed.
+    pub async fn cancel_job(&self, job_id: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .request("POST", &format!("/jobs/{job_id}/cancel"), None)
             .await?;
-        Ok(resp.data)
+        Ok(())
     }
 
-    /// `GET /jobs/{job_id}/audio-tracks/{track_index}` -- retrieve a single audio track transcript.
-    pub async fn get_audio_track(
-        &self,
-        job_id: &str,
-        track_index: u32,
-    ) -> Result<AudioTrackTranscript> {
-        let resp: GetAudioTrackResponse = self
-            .request(
-                "GET",
-                &format!("/jobs/{job_id}/audio-tracks/{track_index}"),
-                None,
-            )
+    /// Just the transcript of a job, without building a full [`ProcessingResult`].
+    /// The API has no transcript-only endpoint, so this still fetches the whole job, but
+    /// parses the transcript straight out of the response without copying the payload.
+    /// Empty if the job isn't complete.
+    pub async fn get_transcript(&self, job_id: &str) -> Result<Vec<TranscriptSegment>> {
+        let resp: GetJobResponse = self
+            .request("GET", &format!("/jobs/{job_id}"), None)
             .await?;
-        Ok(resp.data)
+        self.check_processed_data(&resp.data)?;
+        Ok(transcript_from_value(&resp.data))
+    }
+
+    /// Poll an existing job until it completes. Useful for resuming after a crash when
+    /// the job id was persisted, or for waiting on a job submitted elsewhere.
+    ///
+    /// Errors: `Timeout`, `JobFailed`.
+    pub async fn wait_for_job(
+        &self,
+        job_id: &str,
+        opts: &ProcessOptions,
+    ) -> Result<ProcessingResult> {
+        let job = self.poll(job_id, opts, Job::is_complete).await?;
+        self.result_from_value(job.raw)
+    }
+
+    /// [`wait_for_job`](Self::wait_for_job) with default options and a poll `timeout`.
+    pub async fn wait_for_job_timeout(
+        &self,
+        job_id: &str,
+        timeout: Duration,
+    ) -> Result<ProcessingResult> {
+        let opts = ProcessOptions {
+            timeout,
+            ..Default::default()
+        };
+        self.wait_for_job(job_id, &opts).await
+    }
+
+    /// Poll several jobs at once and return the first to reach a terminal status, along
+    /// with its id. Polling of the remaining jobs stops when this returns.
+    ///
+    /// Errors: `Timeout`, `JobFailed` (if the first job to finish failed), or `Config`
+    /// if `job_ids` is empty.
+    pub async fn wait_any(
+        &self,
+        job_ids: &[String],
+        opts: &ProcessOptions,
+    ) -> Result<(String, ProcessingResult)> {
+        if job_ids.is_empty() {
+            return Err(FrameQueryError::config(
+                "wait_any needs at least one job id",
+            ));
+        }
+        let polls = job_ids.iter().map(|id| {
+            Box::pin(async move {
+                let result = self.wait_for_job(id, opts).await?;
+                Ok((id.clone(), result))
+            })
+        });
+        let (first, _, _) = futures::future::select_all(polls).await;
+        first
+    }
+
+    /// Poll several jobs concurrently until every one is done. One job failing doesn't
+    /// affect the rest. Results are in input order.
+    pub async fn wait_all(
+        &self,
+        job_ids: &[String],
+        opts: &ProcessOptions,
+    ) -> Vec<(String, Result<ProcessingResult>)> {
+        let polls = job_ids.iter().map(|id| async move {
+            let result = self.wait_for_job(id, opts).await;
+            (id.clone(), result)
+        });
+        futures::future::join_all(polls).await
+    }
+
+    /// Poll until the job completes or `opts.until` returns `true`, then return the
+    /// current `Job`. Without `until` this waits for completion like
+    /// [`wait_for_job`](Self::wait_for_job).
+    ///
+    /// Errors: `Timeout`, `JobFailed`.
+    pub async fn wait_until(&self, job_id: &str, opts: &ProcessOptions) -> Result<Job> {
+        let job = self
+            .poll(job_id, opts, |job| {
+                job.is_complete() || opts.until.as_ref().is_some_and(|until| until(job))
+            })
+            .await?;
+        Ok(self.finish_job(job))
+    }
+
+    /// `GET /jobs` with optional `limit`, `cursor`, and `status` filter.
+    /// See [`list_jobs_with`](Self::list_jobs_with) for more filters.
+    pub async fn list_jobs(
+        &self,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+        status: Option<&str>,
+    ) -> Result<JobPage> {
+        self.list_jobs_with(&ListJobsOptions {
+            limit,
+            cursor: cursor.map(String::from),
+            status: status.map(String::from),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// `GET /jobs` with any combination of [`ListJobsOptions`] filters.
+    pub async fn list_jobs_with(&self, opts: &ListJobsOptions) -> Result<JobPage> {
+        let limit = opts.limit.map(|l| l.to_string());
+        let fields = opts.fields.as_ref().map(|f| f.join(","));
+        let tags = opts.tags.as_ref().map(|t| t.join(","));
+        let params = [
+            ("limit", limit.as_deref()),
+            ("cursor", opts.cursor.as_deref()),
+            ("status", opts.status.as_deref()),
+            ("createdAfter", opts.created_after.as_deref()),
+            ("createdBefore", opts.created_before.as_deref()),
+            ("fields", fields.as_deref()),
+            ("tags", tags.as_deref()),
+        ];
+        let path = with_query("/jobs", &params);
+
+        let resp: ListJobsResponse = match self.request("GET", &path, None).await {
+            Ok(resp) => resp,
+            Err(FrameQueryError::Api {
+                status_code,
+                message,
+                body,
+            }) if opts.cursor.is_some()
+                && (status_code == 410
+                    || status_code == 400 && is_cursor_error(&message, body.as_ref())) =>
+            {
+                return Err(FrameQueryError::InvalidCursor {
+                    cursor: opts.cursor.clone().unwrap_or_default(),
+                    status_code,
+                    message,
+                })
+            }
+            Err(e) => return Err(e),
+        };
+
+        let jobs = resp
+            .data
+            .into_iter()
+            .map(|v| self.finish_job(job_from_value(v)))
+            .collect();
+
+        Ok(JobPage {
+            jobs,
+            next_cursor: resp.next_cursor,
+            total: resp.total,
+        })
+    }
+
+    /// Resume a listing from a previously persisted `JobPage::next_cursor`.
+    ///
+    /// Cursors may expire. A stale cursor fails with `InvalidCursor`; restart from the
+    /// first page with `list_jobs` when that happens.
+    pub async fn list_jobs_from(
+        &self,
+        cursor: &str,
+        limit: Option<u32>,
+        status: Option<&str>,
+    ) -> Result<JobPage> {
+        self.list_jobs(limit, Some(cursor), status).await
+    }
+
+    /// Follow `list_jobs` cursors until exhausted and return every job.
+    ///
+    /// Errors with `TooManyJobs` rather than returning a partial list if more than
+    /// `max_jobs` jobs match.
+    pub async fn list_all_jobs(&self, status: Option<&str>, max_jobs: usize) -> Result<Vec<Job>> {
+        let mut jobs = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let page = self.list_jobs(None, cursor.as_deref(), status).await?;
+            jobs.extend(page.jobs);
+
+            if jobs.len() > max_jobs {
+                return Err(FrameQueryError::TooManyJobs { limit: max_jobs });
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(jobs),
+            }
+        }
+    }
+
+    /// Cancel every job whose status is `status`, e.g. `"PENDING_UPLOAD"` after a bad
+    /// batch run. Lists all matching jobs first (following cursors), then cancels them
+    /// one by one; a failed cancel is recorded in the summary and the sweep carries on.
+    ///
+    /// Errors: only if listing fails, in which case nothing has been cancelled.
+    pub async fn cancel_jobs_where(&self, status: &str) -> Result<CancelSummary> {
+        let mut ids = Vec::new();
+        let mut opts = ListJobsOptions {
+            status: Some(status.to_string()),
+            fields: Some(vec!["jobId".to_string()]),
+            ..Default::default()
+        };
+        loop {
+            let page = self.list_jobs_with(&opts).await?;
+            ids.extend(page.jobs.into_iter().map(|job| job.id));
+            match page.next_cursor {
+                Some(next) => opts.cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let mut summary = CancelSummary::default();
+        for id in ids {
+            match self.cancel_job(&id).await {
+                Ok(()) => summary.cancelled.push(id),
+                Err(e) => summary.failed.push((id, e)),
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Pre-flight check that the API is reachable and the key is valid, e.g. before a
+    /// long batch. Sends `GET /quota`, the cheapest authenticated call, and ignores the
+    /// body. `Ok` only for a 200.
+    ///
+    /// Errors: `Authentication` for a bad key, `Http` if the API can't be reached, `Api`
+    /// for any other 2xx, or whatever error the response maps to.
+    pub async fn ping(&self) -> Result<()> {
+        if self.transport.is_some() {
+            return self
+                .request::<serde_json::Value>("GET", "/quota", None)
+                .await
+                .map(|_| ());
+        }
+        let url = format!("{}/quota", self.base_url);
+        let endpoint = endpoint_label("GET", "/quota");
+        self.until_deadline(async {
+            let (response, attempt_started) = self
+                .send_with_retry(
+                    &self.http,
+                    "GET",
+                    &url,
+                    &endpoint,
+                    self.auth_headers(),
+                    None,
+                )
+                .await?;
+            let status = response.status();
+            self.log_response(status.as_u16(), attempt_started.elapsed(), "");
+            if status != reqwest::StatusCode::OK {
+                return Err(FrameQueryError::Api {
+                    status_code: status.as_u16(),
+                    message: format!("ping expected 200 from GET /quota, got {status}"),
+                    body: None,
+                });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// `GET /quota`.
+    pub async fn get_quota(&self) -> Result<Quota> {
+        let resp: GetQuotaResponse = self.request("GET", "/quota", None).await?;
+        Ok(resp.data)
+    }
+
+    /// `GET /jobs/{job_id}/audio-tracks` -- retrieve all audio track transcripts.
+    pub async fn get_audio_tracks(&self, job_id: &str) -> Result<Vec<AudioTrackTranscript>> {
+        let resp: GetAudioTracksResponse = self
+            .request("GET", &format!("/jobs/{job_id}/audio-tracks"), None)
+            .await?;
+        Ok(resp.data)
+    }
+
+    /// `GET /jobs/{job_id}/audio-tracks/{track_index}` -- retrieve a single audio track transcript.
+    pub async fn get_audio_track(
+        &self,
+        job_id: &str,
+        track_index: u32,
+    ) -> Result<AudioTrackTranscript> {
+        let resp: GetAudioTrackResponse = self
+            .request(
+                "GET",
+                &format!("/jobs/{job_id}/audio-tracks/{track_index}"),
+                None,
+            )
+            .await?;
+        Ok(resp.data)
+    }
+
+    /// Download a processing artifact. The URL is read from the job payload; relative
+    /// URLs resolve against the base URL. The API key is only sent to the API's own
+    /// origin (same scheme, host, and port), never to third-party (e.g. pre-signed
+    /// storage) URLs. Retried like other requests until the body starts arriving.
+    ///
+    /// Errors: `NotFound` if the job has no artifact of that kind.
+    pub async fn download_artifact(
+        &self,
+        job_id: &str,
+        kind: ArtifactKind,
+    ) -> Result<bytes::Bytes> {
+        let job = self.fetch_job(job_id).await?;
+        let url = artifact_url(&job.raw, kind).ok_or_else(|| FrameQueryError::NotFound {
+            message: format!("job {job_id} has no {} artifact", kind.key()),
+        })?;
+
+        let url = if url.starts_with('/') {
+            format!("{}{}", self.base_url, url)
+        } else {
+            url.to_string()
+        };
+
+        let headers = if same_origin(&url, &self.base_url) {
+            self.auth_headers()
+        } else {
+            HeaderMap::new()
+        };
+        let endpoint = format!("GET {{{}_artifact}}", kind.key());
+
+        self.until_deadline(async {
+            let (response, attempt_started) = self
+                .send_with_retry(&self.upload_http, "GET", &url, &endpoint, headers, None)
+                .await?;
+            self.log_response(response.status().as_u16(), attempt_started.elapsed(), "");
+            response.bytes().await.map_err(FrameQueryError::Http)
+        })
+        .await
+    }
+
+    /// Stream the raw `GET /jobs/{job_id}` response body into `writer`, without buffering
+    /// or parsing it, and return the number of bytes written. For archiving large
+    /// results to disk; call it once the job is complete. Retried like other API requests
+    /// until the body starts arriving; a failure mid-body isn't, as `writer` already
+    /// holds part of it. Bounded by [`upload_timeout`](ClientBuilder::upload_timeout)
+    /// rather than the API `timeout`, which would cut off large bodies.
+    ///
+    /// ```no_run
+    /// # async fn run(client: framequery::Client) -> framequery::Result<()> {
+    /// let mut file = tokio::fs::File::create("result.json").await?;
+    /// client.download_result_to("job_1", &mut file).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_result_to(
+        &self,
+        job_id: &str,
+        mut writer: impl tokio::io::AsyncWrite + Unpin,
+    ) -> Result<u64> {
+        use tokio::io::AsyncWriteExt;
+
+        let path = format!("/jobs/{job_id}");
+        if let Some(ref transport) = self.transport {
+            let body = serde_json::to_vec(&transport.request("GET", &path, None)?)
+                .expect("serde_json::Value always serializes");
+            writer.write_all(&body).await?;
+            writer.flush().await?;
+            return Ok(body.len() as u64);
+        }
+
+        let url = format!("{}{path}", self.base_url);
+        let endpoint = endpoint_label("GET", &path);
+        self.until_deadline(async {
+            let (mut response, attempt_started) = self
+                .send_with_retry(
+                    &self.upload_http,
+                    "GET",
+                    &url,
+                    &endpoint,
+                    self.auth_headers(),
+                    None,
+                )
+                .await?;
+            self.log_response(response.status().as_u16(), attempt_started.elapsed(), "");
+
+            let mut written = 0;
+            while let Some(chunk) = response.chunk().await.map_err(FrameQueryError::Http)? {
+                writer.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+            writer.flush().await?;
+            Ok(written)
+        })
+        .await
     }
 
     /// `POST /jobs/batch` -- submit multiple clips at once.
@@ -338,6 +1576,7 @@ impl Client {
             body["processingMode"] = json!(pm);
         }
         if let Some(ref cb) = opts.callback_url {
+            check_callback_url(cb)?;
             body["callbackUrl"] = json!(cb);
         }
 
@@ -358,27 +1597,40 @@ impl Client {
     }
 
     /// Submit a batch then poll all jobs until every one completes (or one fails).
+    /// [`shutdown`](Self::shutdown) stops it with `Cancelled` for the batch's first job.
     pub async fn process_batch(&self, opts: BatchOptions) -> Result<Vec<ProcessingResult>> {
         let batch = self.create_batch(&opts).await?;
-        let deadline = Instant::now() + opts.timeout;
-        let job_ids: Vec<String> = batch.jobs.iter().map(|j| j.job_id.clone()).collect();
+        let job_ids: Vec<&str> = batch.jobs.iter().map(|j| j.job_id.as_str()).collect();
+        self.shutdown
+            .track(&job_ids, self.poll_batch(&job_ids, &opts))
+            .await
+    }
+
+    async fn poll_batch(
+        &self,
+        job_ids: &[&str],
+        opts: &BatchOptions,
+    ) -> Result<Vec<ProcessingResult>> {
+        let (deadline, limit) = self.poll_deadline(opts.timeout);
+        let started = Instant::now();
         let mut results: std::collections::HashMap<String, ProcessingResult> =
             std::collections::HashMap::new();
 
         loop {
-            for job_id in &job_ids {
+            for &job_id in job_ids {
                 if results.contains_key(job_id) {
                     continue;
                 }
-                let job = self.get_job(job_id).await?;
+                let job = self.fetch_job(job_id).await?;
+                if job.is_terminal() {
+                    self.metrics.on_job_finished(&job.status, started.elapsed());
+                }
                 if job.is_failed() {
-                    return Err(FrameQueryError::JobFailed(format!(
-                        "batch job {} failed",
-                        job_id
-                    )));
+                    return Err(FrameQueryError::job_failed(&job));
                 }
                 if job.is_complete() {
-                    results.insert(job_id.clone(), processing_result_from_value(job.raw));
+                    let result = self.result_from_value(job.raw)?;
+                    results.insert(job_id.to_string(), result);
                 }
             }
 
@@ -387,116 +1639,698 @@ impl Client {
             }
 
             if Instant::now() >= deadline {
-                return Err(FrameQueryError::Timeout(opts.timeout));
+                return Err(FrameQueryError::Timeout(limit));
             }
-            tokio::time::sleep(opts.poll_interval).await;
+            tokio::time::sleep(poll_delay(opts.poll_interval, deadline)).await;
         }
 
         Ok(job_ids
             .iter()
-            .map(|id| results.remove(id).unwrap())
+            .map(|&id| results.remove(id).unwrap())
             .collect())
     }
 
+    /// Stop all polling on this client and its clones: running `process`/`wait_*`/
+    /// `process_batch` calls return `Cancelled`, `process_with_events` and
+    /// `process_streaming_transcript` streams end with it, and so do later ones.
+    ///
+    /// Returns the ids of jobs that were still being polled, so they can be persisted and
+    /// resumed with `wait_for_job` after a restart. Remote jobs are not cancelled, and a
+    /// [`JobHandle`] whose `wait` returns `Cancelled` is disarmed so dropping it doesn't
+    /// cancel its job either. A handle that was never waited on still cancels on drop.
+    pub fn shutdown(&self) -> Vec<String> {
+        self.shutdown.trigger()
+    }
+
+    /// Rate-limit headers from the most recent successful API response that had them.
+    /// Shared across clones of this client.
+    pub fn last_rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
+    /// All headers of the most recent API response, successful or not, e.g. to check
+    /// `X-Cache` or `Age` in a support case. Shared across clones of this client.
+    pub fn last_response_headers(&self) -> Option<HeaderMap> {
+        self.last_headers.lock().unwrap().clone()
+    }
+
+    /// Call any endpoint with the client's auth and retry behavior and return the raw
+    /// JSON response. `path` is relative to the base URL, e.g. `"/jobs/abc/thumbnails"`.
+    /// An escape hatch for endpoints the SDK doesn't model yet. `method` is one of `GET`,
+    /// `POST`, `PUT`, `PATCH`, or `DELETE`, in any case.
+    ///
+    /// Errors: `Config` for any other method.
+    pub async fn raw_request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let method = method.to_ascii_uppercase();
+        http_method(&method)?;
+        self.request(&method, path, body).await
+    }
+
     // -----------------------------------------------------------------------
     // Private helpers
     // -----------------------------------------------------------------------
 
-    /// HTTP request with retry. Retries 5xx, 429, and network errors.
-    /// Backoff: 1s, 2s, 4s, ... capped at 32s.
+    /// The name of the API key header for the configured [`AuthScheme`].
+    fn auth_header_name(&self) -> HeaderName {
+        match self.auth_scheme {
+            AuthScheme::Bearer => AUTHORIZATION,
+            // Validated in `ClientBuilder::build`.
+            AuthScheme::ApiKeyHeader(ref name) => {
+                HeaderName::from_bytes(name.as_bytes()).expect("invalid auth header name")
+            }
+        }
+    }
+
+    /// The API key header for the configured [`AuthScheme`].
+    fn auth_headers(&self) -> HeaderMap {
+        let value = match self.auth_scheme {
+            AuthScheme::Bearer => format!("Bearer {}", self.api_key),
+            AuthScheme::ApiKeyHeader(_) => self.api_key.clone(),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            self.auth_header_name(),
+            HeaderValue::from_str(&value).expect("invalid API key characters"),
+        );
+        headers
+    }
+
+    /// Best-effort cancel of a job whose upload failed, so it isn't left in
+    /// `PENDING_UPLOAD`. Skipped under `idempotency_key`, so a retry with the same key
+    /// gets the job back rather than a cancelled one.
+    async fn abandon_job(&self, job_id: &str, opts: Option<&ProcessOptions>) {
+        if opts.is_some_and(|o| o.idempotency_key.is_some()) {
+            return;
+        }
+        let _ = self.cancel_job(job_id).await;
+    }
+
+    /// Send bytes to a job's signed URL: a raw `PUT`, or a multipart `POST` with the
+    /// job's `uploadFields` if the server asked for one. Retries 5xx and network errors
+    /// against the same URL with the client's retry settings. With `progress`, the body
+    /// is streamed in chunks and the running total sent there.
+    async fn upload_to_signed_url(
+        &self,
+        target: &CreateJobData,
+        file_name: &str,
+        bytes: &bytes::Bytes,
+        checksum: Option<&(&'static str, String)>,
+        progress: Option<&watch::Sender<u64>>,
+    ) -> Result<()> {
+        check_upload_url(target, self.allow_insecure)?;
+        let len = bytes.len() as u64;
+        self.until_deadline(async {
+            let mut attempt = 0;
+            loop {
+                let body = match progress {
+                    Some(progress) => counting_body(bytes, progress),
+                    None => bytes.clone().into(),
+                };
+                let req = self.signed_upload_request(target, file_name, body, len, checksum);
+                let result = match self.signed_upload_outcome(req.send().await, len).await {
+                    Ok(()) => return Ok(()),
+                    Err(e) => e,
+                };
+
+                if attempt >= self.max_retries || !self.should_retry(&result) {
+                    return Err(result);
+                }
+                attempt += 1;
+                tokio::time::sleep(backoff_for(attempt, self.max_backoff)).await;
+            }
+        })
+        .await
+    }
+
+    /// The request sending `body` (`len` bytes) to `target`'s signed URL: a raw `PUT`,
+    /// or a multipart `POST` if the API asked for one.
+    fn signed_upload_request(
+        &self,
+        target: &CreateJobData,
+        file_name: &str,
+        body: reqwest::Body,
+        len: u64,
+        checksum: Option<&(&'static str, String)>,
+    ) -> reqwest::RequestBuilder {
+        let use_post = target
+            .upload_method
+            .as_deref()
+            .is_some_and(|m| m.eq_ignore_ascii_case("POST"));
+        if use_post {
+            // The file must be the last form field.
+            let mut form = reqwest::multipart::Form::new();
+            for (name, value) in target.upload_fields.iter().flatten() {
+                form = form.text(name.clone(), value.clone());
+            }
+            // S3-style POST policies take the checksum as a form field, not a header.
+            if let Some((field, digest)) = checksum {
+                let already_set = target
+                    .upload_fields
+                    .iter()
+                    .flatten()
+                    .any(|(name, _)| name.eq_ignore_ascii_case(field));
+                if !already_set {
+                    form = form.text(*field, digest.clone());
+                }
+            }
+            let file = reqwest::multipart::Part::stream_with_length(body, len)
+                .file_name(file_name.to_string());
+            self.upload_http
+                .post(&target.upload_url)
+                .multipart(form.part("file", file))
+        } else {
+            // Explicit, since a streamed body would otherwise go out chunked, which
+            // signed URLs reject.
+            let mut req = self
+                .upload_http
+                .put(&target.upload_url)
+                .header(CONTENT_TYPE, "application/octet-stream")
+                .header(CONTENT_LENGTH, len);
+            if let Some((header, digest)) = checksum {
+                req = req.header(*header, digest);
+            }
+            req.body(body)
+        }
+    }
+
+    /// `Ok` for a 2xx from a signed-URL upload of `len` bytes; otherwise `FileTooLarge`
+    /// if the storage backend said so, else `Api` / `Http`.
+    async fn signed_upload_outcome(
+        &self,
+        sent: reqwest::Result<reqwest::Response>,
+        len: u64,
+    ) -> Result<()> {
+        match sent {
+            Ok(resp) if resp.status().is_success() => {
+                self.metrics.on_upload(len);
+                Ok(())
+            }
+            Ok(resp) => {
+                let status = resp.status().as_u16();
+                let text = resp.text().await.unwrap_or_default();
+                // S3 says `EntityTooLarge` with a 400.
+                if status == 413 || text.contains("EntityTooLarge") {
+                    return Err(FrameQueryError::FileTooLarge {
+                        size: Some(len),
+                        limit: None,
+                        message: format!("signed URL rejected the upload: {text}"),
+                    });
+                }
+                Err(FrameQueryError::Api {
+                    status_code: status,
+                    message: format!("upload to signed URL failed: {text}"),
+                    body: None,
+                })
+            }
+            Err(e) => Err(FrameQueryError::Http(e)),
+        }
+    }
+
+    /// `job`, minus `raw` unless `retain_raw` is on. For values handed to the caller,
+    /// once the SDK is done reading `raw`.
+    fn finish_job(&self, mut job: Job) -> Job {
+        if !self.retain_raw {
+            job.raw = serde_json::Value::Null;
+        }
+        job
+    }
+
+    /// Build the result handed to the caller: checked first under `strict_parsing`, and
+    /// minus `raw` unless `retain_raw` is on.
+    fn result_from_value(&self, val: serde_json::Value) -> Result<ProcessingResult> {
+        self.check_processed_data(&val)?;
+        let mut result = processing_result_from_value(val);
+        if !self.retain_raw {
+            result.raw = serde_json::Value::Null;
+        }
+        Ok(result)
+    }
+
+    /// Under `strict_parsing`, `Decode` if `processedData` has a malformed scenes or
+    /// transcript field.
+    fn check_processed_data(&self, val: &serde_json::Value) -> Result<()> {
+        if !self.strict_parsing {
+            return Ok(());
+        }
+        match processed_data_mismatch(val) {
+            Some((field, err, found)) => Err(FrameQueryError::Decode {
+                message: format!("processedData.{field} has an unexpected shape: {err}"),
+                body_snippet: snippet(&found.to_string()),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The auth header as `name: value` with the key redacted, for `debug_log`.
+    fn redacted_auth_header(&self) -> String {
+        match self.auth_scheme {
+            AuthScheme::Bearer => format!("authorization: Bearer {}", redact_key(&self.api_key)),
+            AuthScheme::ApiKeyHeader(ref name) => format!("{name}: {}", redact_key(&self.api_key)),
+        }
+    }
+
+    /// `debug_log` line for a response.
+    fn log_response(&self, status: u16, elapsed: Duration, body: &str) {
+        if self.debug_log {
+            eprintln!(
+                "[framequery] <- {status} in {elapsed:?} body={}B {}",
+                body.len(),
+                snippet(body)
+            );
+        }
+    }
+
+    /// Run a user callback, catching a panic so polling carries on. The callbacks only
+    /// get shared references, which a panic can't leave half-modified, so that's sound.
+    /// The panic hook still reports the panic; `debug_log` also logs its message.
+    fn run_callback(&self, name: &str, cb: impl FnOnce()) {
+        let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(cb)) else {
+            return;
+        };
+        if self.debug_log {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("(non-string panic payload)");
+            eprintln!("[framequery] {name} callback panicked: {message}");
+        }
+    }
+
+    /// Run `opts.on_job_created`, if set.
+    fn job_created(&self, opts: &ProcessOptions, job_id: &str) {
+        if let Some(ref cb) = opts.on_job_created {
+            self.run_callback("on_job_created", || cb(job_id));
+        }
+    }
+
+    /// `err` is retryable by default or has a status code from `retry_on`.
+    fn should_retry(&self, err: &FrameQueryError) -> bool {
+        err.is_retryable()
+            || matches!(
+                err,
+                FrameQueryError::Api { status_code, .. } if self.retry_on.contains(status_code)
+            )
+    }
+
+    /// Run `fut`, or fail with `Timeout` if the `with_deadline` deadline passes first.
+    /// Wraps whole operations, body reads included, not just sending the request.
+    async fn until_deadline<T>(&self, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        let Some(deadline) = self.deadline else {
+            return fut.await;
+        };
+        let started = Instant::now();
+        tokio::time::timeout_at(deadline, fut)
+            .await
+            .unwrap_or_else(|_| Err(FrameQueryError::Timeout(started.elapsed())))
+    }
+
+    /// When polling that starts now must stop: after `timeout`, or at the client's
+    /// `with_deadline` if that's sooner. Also returns the resulting time budget.
+    fn poll_deadline(&self, timeout: Duration) -> (Instant, Duration) {
+        let now = Instant::now();
+        let deadline = match self.deadline {
+            Some(d) => d.min(now + timeout),
+            None => now + timeout,
+        };
+        (deadline, deadline.saturating_duration_since(now))
+    }
+
+    /// The first status check: waits `opts.initial_delay`, then tolerates a couple of
+    /// 404s, since a just-created job may not be queryable yet. Each 404 retried adds
+    /// one to `polls`, so they count toward `max_polls`; the caller counts the last check.
+    async fn first_poll(
+        &self,
+        job_id: &str,
+        opts: &ProcessOptions,
+        deadline: Instant,
+        cache: &mut EtagCache,
+        polls: &mut u32,
+    ) -> Result<Job> {
+        tokio::time::sleep(poll_delay(opts.initial_delay, deadline)).await;
+        let mut misses = 0;
+        loop {
+            match self.poll_job(job_id, opts.light_polling, cache).await {
+                Err(FrameQueryError::NotFound { .. })
+                    if misses < POLL_NOT_FOUND_RETRIES
+                        && Instant::now() < deadline
+                        && opts.max_polls.is_none_or(|max| *polls + 1 < max) =>
+                {
+                    misses += 1;
+                    *polls += 1;
+                    tokio::time::sleep(poll_delay(opts.poll_interval, deadline)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// One status check. With `light`, fetches the status-only view, plus the full job
+    /// once it's complete. Sends `If-None-Match` when `cache` holds an `ETag` from the
+    /// previous check and reuses the cached job on a 304; servers that send no `ETag`
+    /// just leave `cache` empty.
+    async fn poll_job(&self, job_id: &str, light: bool, cache: &mut EtagCache) -> Result<Job> {
+        let path = if light {
+            format!("/jobs/{job_id}?light=true")
+        } else {
+            format!("/jobs/{job_id}")
+        };
+        let etag = cache.as_ref().map(|c| c.0.as_str());
+        let job = match self
+            .request_conditional::<GetJobResponse>("GET", &path, None, etag)
+            .await?
+        {
+            Some((resp, etag)) => {
+                let job = job_from_value(resp.data);
+                *cache = etag.map(|tag| Box::new((tag, job.clone())));
+                job
+            }
+            None => match cache.as_ref() {
+                Some(c) => c.1.clone(),
+                None => return Err(unexpected_not_modified()),
+            },
+        };
+        if light && job.is_complete() {
+            return self.fetch_job(job_id).await;
+        }
+        Ok(job)
+    }
+
+    /// Poll until `done(&job)`, a FAILED status, timeout, or [`shutdown`](Self::shutdown).
+    async fn poll(
+        &self,
+        job_id: &str,
+        opts: &ProcessOptions,
+        done: impl Fn(&Job) -> bool,
+    ) -> Result<Job> {
+        self.shutdown
+            .track(&[job_id], self.poll_until(job_id, opts, done))
+            .await
+    }
+
+    async fn poll_until(
+        &self,
+        job_id: &str,
+        opts: &ProcessOptions,
+        done: impl Fn(&Job) -> bool,
+    ) -> Result<Job> {
+        let (deadline, limit) = self.poll_deadline(opts.timeout);
+        let started = Instant::now();
+        let mut polls = 0;
+        let mut cache = None;
+        // Predicted completion, in seconds after `started`.
+        let mut smoothed: Option<f64> = None;
+
+        loop {
+            let job = if polls == 0 {
+                self.first_poll(job_id, opts, deadline, &mut cache, &mut polls)
+                    .await?
+            } else {
+                self.poll_job(job_id, opts.light_polling, &mut cache)
+                    .await?
+            };
+            polls += 1;
+
+            if job.is_terminal() {
+                self.metrics.on_job_finished(&job.status, started.elapsed());
+            }
+
+            if let Some(ref cb) = opts.on_progress {
+                self.run_callback("on_progress", || cb(&job));
+            }
+            if let Some(ref cb) = opts.on_update {
+                if let Some(eta) = job.eta_seconds.filter(|eta| eta.is_finite()) {
+                    let predicted = started.elapsed().as_secs_f64() + eta.max(0.0);
+                    smoothed =
+                        Some(smoothed.map_or(predicted, |s| s + ETA_SMOOTHING * (predicted - s)));
+                }
+                let update = ProgressUpdate {
+                    job: &job,
+                    estimated_completion: smoothed
+                        .map(|s| (started + Duration::from_secs_f64(s)).into_std()),
+                };
+                self.run_callback("on_update", || cb(&update));
+            }
+
+            if job.is_failed() {
+                return Err(FrameQueryError::job_failed(&job));
+            }
+
+            if done(&job) {
+                return Ok(job);
+            }
+
+            if let Some(err) = poll_limit_error(opts, polls, deadline, limit) {
+                return Err(err);
+            }
+
+            tokio::time::sleep(poll_delay(opts.poll_interval, deadline)).await;
+        }
+    }
+
+    /// HTTP request with retry. Retries 5xx, 429, 408, `retry_on` codes, and network errors.
+    /// Backoff: 1s, 2s, 4s, ... capped at `max_backoff`, or the 429 body's `retryAfter`
+    /// (returned as an error immediately if it exceeds `max_backoff`). Gives up early if
+    /// `max_retry_elapsed` would be exceeded, and with `Timeout` if the `with_deadline`
+    /// deadline passes.
     async fn request<T: DeserializeOwned>(
         &self,
         method: &str,
         path: &str,
         body: Option<serde_json::Value>,
     ) -> Result<T> {
+        match self.request_conditional(method, path, body, None).await? {
+            Some((value, _)) => Ok(value),
+            None => Err(unexpected_not_modified()),
+        }
+    }
+
+    /// [`request`](Self::request), plus the response's `ETag`. With `if_none_match`, sends
+    /// `If-None-Match` and returns `Ok(None)` on a 304. Custom transports never see the
+    /// header and never report an `ETag`.
+    async fn request_conditional<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<serde_json::Value>,
+        if_none_match: Option<&str>,
+    ) -> Result<Option<(T, Option<String>)>> {
+        if let Some(ref transport) = self.transport {
+            let value = transport.request(method, path, body.as_ref())?;
+            return T::deserialize(&value)
+                .map(|v| Some((v, None)))
+                .map_err(|e| FrameQueryError::Decode {
+                    message: format!("transport returned unexpected JSON: {e}"),
+                    body_snippet: snippet(&value.to_string()),
+                });
+        }
+
         let url = format!("{}{}", self.base_url, path);
+        let endpoint = endpoint_label(method, path);
 
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key))
-                .expect("invalid API key characters"),
-        );
+        let mut headers = self.auth_headers();
+        if let Some(val) = if_none_match.and_then(|tag| HeaderValue::from_str(tag).ok()) {
+            headers.insert(IF_NONE_MATCH, val);
+        }
+
+        // Serialize (and compress) once, outside the retry loop.
+        let payload = match body {
+            Some(ref b) => {
+                let json = serde_json::to_vec(b).expect("serde_json::Value always serializes");
+                Some(if self.compression { gzip(&json)? } else { json })
+            }
+            None => None,
+        };
+
+        self.until_deadline(async {
+            let (response, attempt_started) = self
+                .send_with_retry(&self.http, method, &url, &endpoint, headers, payload)
+                .await?;
+            let status = response.status();
+            if status == reqwest::StatusCode::NOT_MODIFIED {
+                self.log_response(status.as_u16(), attempt_started.elapsed(), "");
+                return Ok(None);
+            }
 
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("none")
+                .to_string();
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from);
+            let text = response.text().await.map_err(FrameQueryError::Http)?;
+            self.log_response(status.as_u16(), attempt_started.elapsed(), &text);
+            // A proxy or redirect can slip a 2xx HTML page through; say so plainly.
+            serde_json::from_str(&text)
+                .map(|v| Some((v, etag)))
+                .map_err(|e| FrameQueryError::Decode {
+                    message: format!(
+                        "unexpected {} response body (content-type: {content_type}): {e}",
+                        status.as_u16()
+                    ),
+                    body_snippet: snippet(&text),
+                })
+        })
+        .await
+    }
+
+    /// Send `payload` to `url` through `http` with the client's retries, backoff,
+    /// deadline, metrics, and debug logging. Returns the first 2xx response (or 304, if
+    /// `headers` has `If-None-Match`) with its body unread, plus when that attempt
+    /// started; error responses are read and mapped to typed errors.
+    async fn send_with_retry(
+        &self,
+        http: &reqwest::Client,
+        method: &str,
+        url: &str,
+        endpoint: &str,
+        headers: HeaderMap,
+        payload: Option<Vec<u8>>,
+    ) -> Result<(reqwest::Response, Instant)> {
+        let http_method = http_method(method)?;
+        // Without the key it's a third-party URL, whose headers aren't the API's.
+        let to_api = headers.contains_key(self.auth_header_name());
         let mut last_err: Option<FrameQueryError> = None;
+        let mut retry_after: Option<Duration> = None;
+        // Latest hint any 429 gave, in case the final one didn't include it.
+        let mut latest_hint: Option<f64> = None;
+        let started = Instant::now();
+
+        let deadline_passed = || FrameQueryError::Timeout(started.elapsed());
 
         for attempt in 0..=self.max_retries {
             if attempt > 0 {
-                let backoff = Duration::from_secs(1 << (attempt - 1).min(5));
+                let backoff = retry_after
+                    .take()
+                    .unwrap_or_else(|| backoff_for(attempt, self.max_backoff));
+                if let Some(cap) = self.max_retry_elapsed {
+                    if started.elapsed() + backoff > cap {
+                        break;
+                    }
+                }
+                if self.deadline.is_some_and(|d| Instant::now() + backoff >= d) {
+                    return Err(deadline_passed());
+                }
                 tokio::time::sleep(backoff).await;
+                self.metrics.on_retry(endpoint);
             }
 
-            let mut req = match method {
-                "GET" => self.http.get(&url),
-                "POST" => self.http.post(&url),
-                "PUT" => self.http.put(&url),
-                "DELETE" => self.http.delete(&url),
-                "PATCH" => self.http.patch(&url),
-                _ => self.http.get(&url),
-            };
+            let mut req = http
+                .request(http_method.clone(), url)
+                .headers(headers.clone());
+            if let (Some(t), "POST") = (self.control_plane_timeout, method) {
+                req = req.timeout(t);
+            }
 
-            req = req.headers(headers.clone());
+            if let Some(ref p) = payload {
+                req = req.header(CONTENT_TYPE, "application/json");
+                if self.compression {
+                    req = req.header(CONTENT_ENCODING, "gzip");
+                }
+                req = req.body(p.clone());
+            }
 
-            if let Some(ref b) = body {
-                req = req.header(CONTENT_TYPE, "application/json").json(b);
+            if self.debug_log {
+                eprintln!(
+                    "[framequery] {method} {url} {} body={}B",
+                    if to_api {
+                        self.redacted_auth_header()
+                    } else {
+                        "(no auth)".to_string()
+                    },
+                    payload.as_ref().map_or(0, Vec::len)
+                );
             }
 
-            let response = match req.send().await {
+            let attempt_started = Instant::now();
+            let sent = req.send().await;
+            self.metrics.on_request(
+                endpoint,
+                sent.as_ref().ok().map(|r| r.status().as_u16()),
+                attempt_started.elapsed(),
+            );
+            let response = match sent {
                 Ok(r) => r,
                 Err(e) => {
-                    // Network-level error: retry if we have attempts left.
-                    last_err = Some(FrameQueryError::Http(e));
+                    if self.debug_log {
+                        eprintln!("[framequery] <- request error: {e}");
+                    }
+                    // Connect failures and timeouts are retried; anything else, such as a
+                    // body or redirect error, would fail the same way again.
+                    let err = FrameQueryError::Http(e);
+                    if !err.is_retryable() {
+                        return Err(err);
+                    }
+                    last_err = Some(err);
                     continue;
                 }
             };
 
             let status = response.status();
+            if to_api {
+                *self.last_headers.lock().unwrap() = Some(response.headers().clone());
+            }
 
-            // Successful response: deserialize and return.
+            if status == reqwest::StatusCode::NOT_MODIFIED && headers.contains_key(IF_NONE_MATCH) {
+                return Ok((response, attempt_started));
+            }
             if status.is_success() {
-                let value: T = response.json().await.map_err(FrameQueryError::Http)?;
-                return Ok(value);
+                if let Some(info) =
+                    RateLimitInfo::from_headers(response.headers()).filter(|_| to_api)
+                {
+                    *self.rate_limit.lock().unwrap() = Some(info);
+                }
+                return Ok((response, attempt_started));
             }
 
             // Map well-known error codes to typed errors.
             let status_code = status.as_u16();
+            let header_hint = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<f64>().ok());
             let response_text = response.text().await.unwrap_or_default();
+            self.log_response(status_code, attempt_started.elapsed(), &response_text);
+            let mut err = FrameQueryError::from_response(status_code, &response_text);
+
+            // The body's `retryAfter` wins; the `Retry-After` header (in seconds) is the
+            // fallback.
+            if let FrameQueryError::RateLimit { retry_after, .. } = &mut err {
+                if retry_after.is_none() {
+                    *retry_after = header_hint;
+                }
+                if retry_after.is_some() {
+                    latest_hint = *retry_after;
+                }
+            }
 
-            let parsed_body: Option<serde_json::Value> = serde_json::from_str(&response_text).ok();
-
-            let message = parsed_body
-                .as_ref()
-                .and_then(|b| b.get("error"))
-                .and_then(|e| e.as_str())
-                .unwrap_or(&response_text)
-                .to_string();
-
-            let err = match status_code {
-                401 => FrameQueryError::Authentication { message },
-                403 => FrameQueryError::PermissionDenied { message },
-                404 => FrameQueryError::NotFound { message },
-                429 => {
-                    // Extract Retry-After header if present.
-                    let retry_after = parsed_body
-                        .as_ref()
-                        .and_then(|b| b.get("retryAfter"))
-                        .and_then(|v| v.as_f64());
-
-                    FrameQueryError::RateLimit {
-                        message,
-                        retry_after,
-                    }
+            // Honor the server's retry-after hint, but hand absurd values back to the
+            // caller instead of sleeping on them.
+            if let FrameQueryError::RateLimit {
+                retry_after: Some(secs),
+                ..
+            } = err
+            {
+                let wait = Duration::try_from_secs_f64(secs.max(0.0)).unwrap_or(Duration::MAX);
+                if wait > self.max_backoff {
+                    return Err(err);
                 }
-                _ => FrameQueryError::Api {
-                    status_code,
-                    message,
-                    body: parsed_body,
-                },
-            };
+                retry_after = Some(wait);
+            }
 
-            // Retry on 5xx or 429; return immediately for other errors.
-            if status_code >= 500 || status_code == 429 {
+            // Retry on 5xx, 429, 408, or a `retry_on` code; return immediately otherwise.
+            if self.should_retry(&err) {
                 last_err = Some(err);
                 continue;
             }
@@ -505,40 +2339,626 @@ impl Client {
         }
 
         // All retries exhausted.
-        Err(last_err.unwrap_or_else(|| FrameQueryError::Api {
+        let mut err = last_err.unwrap_or_else(|| FrameQueryError::Api {
             status_code: 0,
             message: "request failed after all retries".into(),
             body: None,
-        }))
+        });
+        if let FrameQueryError::RateLimit { retry_after, .. } = &mut err {
+            if retry_after.is_none() {
+                *retry_after = latest_hint;
+            }
+        }
+        Err(err)
     }
+}
 
-    /// Poll until terminal status or timeout.
-    async fn poll(&self, job_id: &str, opts: &ProcessOptions) -> Result<ProcessingResult> {
-        let deadline = Instant::now() + opts.timeout;
+/// `true` for a job that failed because the backend couldn't fetch its source URL.
+fn is_source_fetch_failure(err: &FrameQueryError) -> bool {
+    match err {
+        FrameQueryError::JobFailed {
+            status, error_code, ..
+        } => {
+            status == "FAILED_FETCH"
+                || matches!(
+                    error_code.as_deref(),
+                    Some("SOURCE_NOT_REACHABLE" | "SOURCE_NOT_FOUND")
+                )
+        }
+        _ => false,
+    }
+}
 
-        loop {
-            let job = self.get_job(job_id).await?;
+/// The header (or, for a multipart `POST`, form field) and base64 digest a signed-URL
+/// upload should carry for `algorithm`, or `None` if it's not one we support.
+///
+/// This is a second pass over the buffered file rather than hashing while streaming: the
+/// digest goes out in a header, before the body, and the algorithm is only known once
+/// the job is created, after the file has been read.
+fn upload_checksum(algorithm: &str, bytes: &[u8]) -> Option<(&'static str, String)> {
+    use base64::Engine;
+    use sha2::Digest;
+
+    let encode = |digest: &[u8]| base64::engine::general_purpose::STANDARD.encode(digest);
+    match algorithm.to_ascii_uppercase().as_str() {
+        "MD5" => Some(("content-md5", encode(&md5::Md5::digest(bytes)))),
+        "SHA256" => Some((
+            "x-amz-checksum-sha256",
+            encode(&sha2::Sha256::digest(bytes)),
+        )),
+        _ => None,
+    }
+}
 
-            if let Some(ref cb) = opts.on_progress {
-                cb(&job);
-            }
+/// `Err(Config)` unless the webhook URL is a valid `https` URL.
+fn check_callback_url(url: &str) -> Result<()> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "https" => Ok(()),
+        _ => Err(FrameQueryError::config(format!(
+            "callback URL must be an https URL, got {url:?}"
+        ))),
+    }
+}
 
-            if job.is_failed() {
-                return Err(FrameQueryError::JobFailed(format!(
-                    "job {} reached FAILED status",
-                    job.id
-                )));
-            }
+/// `POST /jobs` body for uploading `file_name` with `opts`.
+fn create_job_body(file_name: &str, opts: Option<&ProcessOptions>) -> Result<serde_json::Value> {
+    let mut body = json!({ "fileName": file_name });
+    if let Some(o) = opts {
+        if let Some(ref url) = o.callback_url {
+            check_callback_url(url)?;
+            body["callbackUrl"] = json!(url);
+        }
+        if let Some(ref mode) = o.processing_mode {
+            body["processingMode"] = json!(mode);
+        }
+        if let Some(ref key) = o.idempotency_key {
+            body["idempotencyKey"] = json!(key);
+        }
+        if let Some(ref tracks) = o.audio_tracks {
+            body["audioTracks"] = json!(tracks);
+        }
+        if !o.tags.is_empty() {
+            body["tags"] = json!(o.tags);
+        }
+        merge_extra_fields(&mut body, o.extra_fields.as_ref())?;
+    }
+    Ok(body)
+}
 
-            if job.is_complete() {
-                return Ok(processing_result_from_value(job.raw));
-            }
+/// The `Job` returned once an upload is done, before the API has reported on it.
+fn uploaded_job(job_id: String, file_name: String, opts: Option<&ProcessOptions>) -> Job {
+    Job {
+        raw: json!({
+            "jobId": job_id,
+            "status": "PENDING_UPLOAD",
+        }),
+        id: job_id,
+        status: "PENDING_UPLOAD".to_string(),
+        filename: file_name,
+        created_at: String::new(),
+        eta_seconds: None,
+        audio_track_count: None,
+        audio_tracks_completed: None,
+        audio_track_names: Vec::new(),
+        from_cache: false,
+        failure_reason: None,
+        error_code: None,
+        tags: opts.map(|o| o.tags.clone()).unwrap_or_default(),
+        billed_hours: None,
+        progress: None,
+        upload_checksum: None,
+    }
+}
 
-            if Instant::now() >= deadline {
-                return Err(FrameQueryError::Timeout(opts.timeout));
+/// Copy `extra`'s fields into the create-job `body`, skipping any `body` already has.
+/// `Err(Config)` if `extra` isn't a JSON object.
+fn merge_extra_fields(
+    body: &mut serde_json::Value,
+    extra: Option<&serde_json::Value>,
+) -> Result<()> {
+    let Some(extra) = extra else {
+        return Ok(());
+    };
+    let extra = extra.as_object().ok_or_else(|| {
+        FrameQueryError::config("ProcessOptions::extra_fields must be a JSON object")
+    })?;
+    let body = body
+        .as_object_mut()
+        .expect("create-job body is a JSON object");
+    for (key, value) in extra {
+        body.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+    Ok(())
+}
+
+/// `path` plus a percent-encoded query string built from the `Some` params.
+fn with_query(path: &str, params: &[(&str, Option<&str>)]) -> String {
+    let pairs: Vec<(&str, &str)> = params
+        .iter()
+        .filter_map(|&(k, v)| v.map(|v| (k, v)))
+        .collect();
+    if pairs.is_empty() {
+        return path.to_string();
+    }
+    let query = serde_urlencoded::to_string(pairs).expect("string pairs always encode");
+    format!("{path}?{query}")
+}
+
+/// A 304 we have no cached copy for, which only a misbehaving server or proxy sends.
+fn unexpected_not_modified() -> FrameQueryError {
+    FrameQueryError::Api {
+        status_code: 304,
+        message: "unexpected 304 Not Modified with no cached response".to_string(),
+        body: None,
+    }
+}
+
+/// First 200 characters of `text`, for error messages.
+fn snippet(text: &str) -> String {
+    const MAX_CHARS: usize = 200;
+    match text.char_indices().nth(MAX_CHARS) {
+        Some((i, _)) => format!("{}...", &text[..i]),
+        None => text.to_string(),
+    }
+}
+
+/// The `reqwest` method for an uppercase method name the API uses. `Err(Config)` for
+/// anything else, rather than guessing.
+fn http_method(method: &str) -> Result<reqwest::Method> {
+    Ok(match method {
+        "GET" => reqwest::Method::GET,
+        "POST" => reqwest::Method::POST,
+        "PUT" => reqwest::Method::PUT,
+        "PATCH" => reqwest::Method::PATCH,
+        "DELETE" => reqwest::Method::DELETE,
+        other => {
+            return Err(FrameQueryError::config(format!(
+                "unsupported HTTP method {other:?}"
+            )))
+        }
+    })
+}
+
+/// Keep the key's `fq_<env>_` prefix and mask the secret: `fq_live_****`. Only the first
+/// underscore after `fq_` ends the prefix, since the secret may contain underscores too.
+/// Keys without that shape are masked entirely.
+fn redact_key(key: &str) -> String {
+    let prefix = key
+        .strip_prefix("fq_")
+        .and_then(|rest| rest.split_once('_'))
+        .map_or("", |(env, _)| &key[..env.len() + 4]);
+    format!("{prefix}****")
+}
+
+/// Sleep before retry `attempt` (1-based): 1s, 2s, 4s, ... saturating at `max`.
+/// Attempt 0 is the initial request and never waits.
+fn backoff_for(attempt: u32, max: Duration) -> Duration {
+    if attempt == 0 {
+        return Duration::ZERO;
+    }
+    let secs = 1u64.checked_shl(attempt - 1).unwrap_or(u64::MAX);
+    Duration::from_secs(secs).min(max)
+}
+
+/// Strip trailing slashes and append `/v1/api` if `url` has no path at all.
+fn normalize_base_url(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/');
+    match reqwest::Url::parse(trimmed) {
+        Ok(parsed) if parsed.path() == "/" => format!("{trimmed}{DEFAULT_API_PATH}"),
+        _ => trimmed.to_string(),
+    }
+}
+
+/// `true` if a 400's error code or message is about the list cursor, rather than
+/// e.g. a bad filter sent alongside it.
+fn is_cursor_error(message: &str, body: Option<&serde_json::Value>) -> bool {
+    let code = body
+        .and_then(|b| b.get("code").or_else(|| b.get("errorCode")))
+        .and_then(|c| c.as_str())
+        .unwrap_or("");
+    code.to_ascii_lowercase().contains("cursor") || message.to_ascii_lowercase().contains("cursor")
+}
+
+/// `true` if `url` has the same scheme, host, and port as `base`. Unparseable URLs
+/// match nothing.
+fn same_origin(url: &str, base: &str) -> bool {
+    match (reqwest::Url::parse(url), reqwest::Url::parse(base)) {
+        (Ok(url), Ok(base)) => url.origin() == base.origin(),
+        _ => false,
+    }
+}
+
+/// `Err(Config)` unless `url` is `https`, `http` to a loopback host, or any scheme with
+/// `allow_insecure`.
+fn check_base_url(url: &str, allow_insecure: bool) -> Result<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| FrameQueryError::config(format!("invalid base URL {url:?}: {e}")))?;
+    if is_secure(&parsed) || allow_insecure {
+        return Ok(());
+    }
+    Err(FrameQueryError::config(format!(
+        "refusing to send the API key to non-https base URL {url:?}; \
+         use https or ClientBuilder::allow_insecure(true)"
+    )))
+}
+
+/// `https`, or `http` to a loopback host.
+fn is_secure(url: &reqwest::Url) -> bool {
+    let loopback = match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_matches(['[', ']'])
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback()),
+        None => false,
+    };
+    url.scheme() == "https" || (url.scheme() == "http" && loopback)
+}
+
+/// `Err(Api)` naming the job unless the create-job response's signed URL is absolute
+/// and, without `allow_insecure`, `https` (or `http` to a loopback host). Catches a bad
+/// URL before reqwest fails on it with a cryptic error.
+fn check_upload_url(target: &CreateJobData, allow_insecure: bool) -> Result<()> {
+    let url = &target.upload_url;
+    let problem = match reqwest::Url::parse(url) {
+        Err(e) => e.to_string(),
+        Ok(parsed) if !allow_insecure && !is_secure(&parsed) => {
+            format!("scheme {:?} is not https", parsed.scheme())
+        }
+        Ok(_) => return Ok(()),
+    };
+    Err(FrameQueryError::Api {
+        status_code: 0,
+        message: format!(
+            "server returned an invalid upload URL for job {} ({url:?}): {problem}",
+            target.job_id
+        ),
+        body: Some(json!({ "jobId": target.job_id, "uploadUrl": url })),
+    })
+}
+
+/// Gzip-compress `data` at the default level.
+fn gzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut enc = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    enc.write_all(data)?;
+    Ok(enc.finish()?)
+}
+
+/// Redacts the API key.
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("api_key", &redact_key(&self.api_key))
+            .field("max_retries", &self.max_retries)
+            .field("auth_scheme", &self.auth_scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Same as [`Client::new`].
+impl From<&str> for Client {
+    fn from(api_key: &str) -> Self {
+        Self::new(api_key)
+    }
+}
+
+/// The `ETag` from a job's last status check and the job it tagged, for
+/// `If-None-Match` on the next one.
+type EtagCache = Option<Box<(String, Job)>>;
+
+/// State for [`Client::process_with_events`].
+enum EventPhase<'a> {
+    Start,
+    Uploading {
+        total: u64,
+        upload: futures::future::BoxFuture<'a, Result<Job>>,
+        /// Bytes sent so far, from the upload.
+        progress: watch::Receiver<u64>,
+    },
+    Polling {
+        job_id: String,
+        deadline: Instant,
+        limit: Duration,
+        polls: u32,
+        cache: EtagCache,
+        /// When polling began, for `Metrics::on_job_finished`.
+        started: Instant,
+        /// Lists `job_id` for `Client::shutdown` while polling.
+        registration: Registration,
+    },
+    /// Yield one last event, then end the stream.
+    Emit(Box<ProcessEvent>),
+    Done,
+}
+
+/// State for [`Client::process_streaming_transcript`]. `last_end` is the latest
+/// `end_time` yielded so far.
+enum TranscriptPhase {
+    Start,
+    Polling {
+        job_id: String,
+        deadline: Instant,
+        limit: Duration,
+        polls: u32,
+        last_end: f64,
+        cache: EtagCache,
+        registration: Registration,
+    },
+    Done,
+}
+
+/// `Timeout(limit)` or `MaxPollsExceeded` if polling should stop after `polls` status
+/// checks.
+fn poll_limit_error(
+    opts: &ProcessOptions,
+    polls: u32,
+    deadline: Instant,
+    limit: Duration,
+) -> Option<FrameQueryError> {
+    if opts.max_polls.is_some_and(|max| polls >= max) {
+        return Some(FrameQueryError::MaxPollsExceeded(polls));
+    }
+    if Instant::now() >= deadline {
+        return Some(FrameQueryError::Timeout(limit));
+    }
+    None
+}
+
+/// Sleep before the next poll: `interval`, cut short so the final poll lands on `deadline`
+/// rather than up to a full interval past it.
+fn poll_delay(interval: Duration, deadline: Instant) -> Duration {
+    interval.min(deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Terminal `Failed` event for `err`.
+fn failed<'a>(err: FrameQueryError) -> (ProcessEvent, EventPhase<'a>) {
+    (ProcessEvent::Failed(err.to_string()), EventPhase::Done)
+}
+
+/// `bytes` as a body streamed in chunks, sending the running total handed to the
+/// connection to `progress` as it goes.
+fn counting_body(bytes: &bytes::Bytes, progress: &watch::Sender<u64>) -> reqwest::Body {
+    progress.send_if_modified(|sent| std::mem::take(sent) != 0);
+    let state = (0, bytes.clone(), progress.clone());
+    let chunks = futures::stream::unfold(state, |(start, bytes, progress)| async move {
+        if start >= bytes.len() {
+            return None;
+        }
+        // Otherwise a fast connection sends every chunk before a progress reader on the
+        // same thread gets to run, and all it sees is the total.
+        if start > 0 {
+            tokio::task::yield_now().await;
+        }
+        let end = bytes.len().min(start + UPLOAD_CHUNK_SIZE);
+        progress.send_replace(end as u64);
+        let chunk = bytes.slice(start..end);
+        Some((
+            Ok::<_, std::convert::Infallible>(chunk),
+            (end, bytes, progress),
+        ))
+    });
+    reqwest::Body::wrap_stream(chunks)
+}
+
+/// Run `fut`, failing with `Timeout(limit)` if `budget`'s deadline passes first. For
+/// the streams, which can't wrap their whole run in [`with_total_timeout`].
+async fn within_budget<T>(
+    budget: Option<(Instant, Duration)>,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match budget {
+        Some((deadline, limit)) => tokio::time::timeout_at(deadline, fut)
+            .await
+            .unwrap_or(Err(FrameQueryError::Timeout(limit))),
+        None => fut.await,
+    }
+}
+
+/// The name `upload` records for `path`: its basename, else "video.mp4".
+fn upload_file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "video.mp4".to_string())
+}
+
+/// Run `fut`, failing with `Timeout(limit)` if it takes longer than `limit`.
+async fn with_total_timeout<T>(
+    limit: Option<Duration>,
+    fut: impl std::future::Future<Output = Result<T>>,
+) -> Result<T> {
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, fut)
+            .await
+            .unwrap_or(Err(FrameQueryError::Timeout(limit))),
+        None => fut.await,
+    }
+}
+
+/// Last path segment of `url` if it looks like a filename (has an extension), else "video.mp4".
+/// Query strings and fragments are ignored.
+fn file_name_from_url(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/')
+        .next()
+        .filter(|s| !s.is_empty() && s.contains('.'))
+        .unwrap_or("video.mp4")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_redacts_api_key() {
+        let client = Client::new("fq_live_supersecret");
+        let out = format!("{client:?}");
+        assert!(out.contains("fq_live_****"), "{out}");
+        assert!(!out.contains("supersecret"), "{out}");
+
+        let builder = ClientBuilder::new().api_key("nounderscores");
+        let out = format!("{builder:?}");
+        assert!(!out.contains("nounderscores"), "{out}");
+    }
+
+    #[test]
+    fn debug_log_auth_header_has_no_secret_bytes() {
+        let secret = "s3cr_et_9";
+        let key = format!("fq_live_{secret}");
+        for (scheme, expected) in [
+            (AuthScheme::Bearer, "authorization: Bearer fq_live_****"),
+            (
+                AuthScheme::ApiKeyHeader("X-Api-Key".into()),
+                "X-Api-Key: fq_live_****",
+            ),
+        ] {
+            let client = ClientBuilder::new()
+                .api_key(&key)
+                .auth_scheme(scheme)
+                .build()
+                .unwrap();
+            let header = client.redacted_auth_header();
+            assert_eq!(header, expected);
+            // Whatever follows the prefix is mask, never key material.
+            let (_, masked) = header.split_once("fq_live_").unwrap();
+            assert!(masked.chars().all(|c| c == '*'), "{header}");
+        }
+    }
+
+    #[test]
+    fn same_origin_compares_scheme_host_and_port() {
+        let base = "https://api.framequery.com/v1/api";
+        assert!(same_origin("https://api.framequery.com/files/a.png", base));
+        assert!(same_origin("https://api.framequery.com:443/a.png", base));
+        assert!(!same_origin(
+            "https://api.framequery.com.evil.example/a",
+            base
+        ));
+        assert!(!same_origin("http://api.framequery.com/a.png", base));
+        assert!(!same_origin("https://api.framequery.com:8443/a.png", base));
+        assert!(!same_origin("not a url", base));
+    }
+
+    #[test]
+    fn redact_key_keeps_only_the_env_prefix() {
+        assert_eq!(redact_key("fq_live_abc_def_ghi"), "fq_live_****");
+        assert_eq!(redact_key("fq_test_secret"), "fq_test_****");
+        assert_eq!(redact_key("sk_abc_def"), "****");
+        assert_eq!(redact_key("fq_nosecret"), "****");
+    }
+
+    #[test]
+    fn api_key_file_is_trimmed_and_outranks_fn() {
+        let path = std::env::temp_dir().join(format!("fq-key-{}", std::process::id()));
+        std::fs::write(&path, "fq_file_key\n").unwrap();
+
+        let client = ClientBuilder::new()
+            .api_key_file(&path)
+            .api_key_fn(|| Ok("fq_fn_key".into()))
+            .build()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(client.api_key, "fq_file_key");
+
+        let client = ClientBuilder::new()
+            .api_key_fn(|| Ok(" fq_fn_key\t".into()))
+            .build()
+            .unwrap();
+        assert_eq!(client.api_key, "fq_fn_key");
+    }
+
+    #[test]
+    fn build_reports_every_config_problem_at_once() {
+        let err = ClientBuilder::new()
+            .api_key_file("/nonexistent/framequery-key")
+            .base_url("http://api.example.com")
+            .auth_scheme(AuthScheme::ApiKeyHeader("bad header".into()))
+            .build()
+            .err()
+            .unwrap();
+        match err {
+            FrameQueryError::Config { ref errors } => {
+                assert_eq!(errors.len(), 4, "{errors:?}");
+                assert!(errors[0].contains("API key file"));
+                assert!(errors[3].contains("API key is required"));
             }
+            other => panic!("expected Config, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn plain_http_base_url_needs_opt_in_except_on_localhost() {
+        assert!(check_base_url("https://api.example.com", false).is_ok());
+        assert!(check_base_url("http://localhost:8080", false).is_ok());
+        assert!(check_base_url("http://127.0.0.1:8080/v1/api", false).is_ok());
+        assert!(check_base_url("http://[::1]:8080", false).is_ok());
+        assert!(check_base_url("http://api.example.com", true).is_ok());
+
+        let err = check_base_url("http://api.example.com", false).unwrap_err();
+        assert!(matches!(err, FrameQueryError::Config { .. }), "{err}");
+        assert!(check_base_url("not a url", true).is_err());
+    }
 
-            tokio::time::sleep(opts.poll_interval).await;
+    #[test]
+    fn callback_url_must_be_https() {
+        assert!(check_callback_url("https://example.com/hooks/fq").is_ok());
+        assert!(check_callback_url("http://example.com/hooks/fq").is_err());
+        assert!(check_callback_url("example.com/hooks/fq").is_err());
+    }
+
+    #[test]
+    fn extra_fields_never_override_sdk_fields() {
+        let mut body = json!({ "fileName": "a.mp4" });
+        let extra = json!({ "fileName": "evil.mp4", "priority": "high" });
+
+        merge_extra_fields(&mut body, Some(&extra)).unwrap();
+
+        assert_eq!(body, json!({ "fileName": "a.mp4", "priority": "high" }));
+        assert!(merge_extra_fields(&mut body, Some(&json!(["x"]))).is_err());
+    }
+
+    #[test]
+    fn query_values_are_percent_encoded() {
+        let path = with_query(
+            "/jobs",
+            &[
+                ("limit", Some("10")),
+                ("cursor", Some("ab+c/d==")),
+                ("status", None),
+                ("createdAfter", Some("2025-01-01T00:00:00+01:00")),
+            ],
+        );
+        assert_eq!(
+            path,
+            "/jobs?limit=10&cursor=ab%2Bc%2Fd%3D%3D&createdAfter=2025-01-01T00%3A00%3A00%2B01%3A00"
+        );
+        assert_eq!(with_query("/jobs", &[("status", None)]), "/jobs");
+    }
+
+    #[test]
+    fn backoff_doubles_then_saturates() {
+        let max = DEFAULT_MAX_BACKOFF;
+        let expected = [0, 1, 2, 4, 8, 16, 30, 30, 30, 30, 30];
+        for (attempt, secs) in expected.into_iter().enumerate() {
+            assert_eq!(
+                backoff_for(attempt as u32, max),
+                Duration::from_secs(secs),
+                "attempt {attempt}"
+            );
         }
     }
+
+    #[test]
+    fn backoff_never_overflows() {
+        for attempt in [63, 64, 65, 1000, u32::MAX] {
+            assert_eq!(
+                backoff_for(attempt, DEFAULT_MAX_BACKOFF),
+                DEFAULT_MAX_BACKOFF
+            );
+        }
+        assert_eq!(
+            backoff_for(u32::MAX, Duration::MAX),
+            Duration::from_secs(u64::MAX)
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::models::Job;
+
 #[derive(Error, Debug)]
 pub enum FrameQueryError {
     /// HTTP 401.
@@ -10,11 +12,22 @@ pub enum FrameQueryError {
     #[error("permission denied: {message}")]
     PermissionDenied { message: String },
 
+    /// The account is out of processing hours: HTTP 402, or a 403 whose `code` is
+    /// `QUOTA_EXCEEDED`. Prompt the user to top up credits. Hours come from the
+    /// body's `neededHours` / `availableHours`, if present.
+    #[error("quota exceeded: {message}")]
+    QuotaExceeded {
+        message: String,
+        needed_hours: Option<f64>,
+        available_hours: Option<f64>,
+    },
+
     /// HTTP 404.
     #[error("not found: {message}")]
     NotFound { message: String },
 
-    /// HTTP 429. `retry_after` comes from the response body, if present.
+    /// HTTP 429. `retry_after` comes from the response body or `Retry-After` header, if
+    /// present; after exhausted retries, from the latest 429 that had one.
     #[error("rate limited (retry after {retry_after:?}s): {message}")]
     RateLimit {
         message: String,
@@ -29,6 +42,21 @@ pub enum FrameQueryError {
         body: Option<serde_json::Value>,
     },
 
+    /// A successful response body couldn't be parsed, or didn't have the expected shape.
+    /// The server was reached; what it sent wasn't understood. `body_snippet` is the
+    /// start of the offending body.
+    #[error("could not decode response: {message}")]
+    Decode {
+        message: String,
+        body_snippet: String,
+    },
+
+    /// Invalid client configuration, or invalid arguments to a client method.
+    /// `ClientBuilder::build` reports every problem it finds at once; elsewhere `errors`
+    /// has a single entry.
+    #[error("invalid configuration: {}", .errors.join("; "))]
+    Config { errors: Vec<String> },
+
     /// reqwest transport error (DNS, TLS, connection reset, etc.).
     #[error("request failed: {0}")]
     Http(#[from] reqwest::Error),
@@ -41,9 +69,207 @@ pub enum FrameQueryError {
     #[error("poll timed out after {0:?}")]
     Timeout(std::time::Duration),
 
-    /// Job status became `FAILED`.
-    #[error("job failed: {0}")]
-    JobFailed(String),
+    /// Polling hit `ProcessOptions::max_polls` before the job finished.
+    #[error("job not finished after {0} polls")]
+    MaxPollsExceeded(u32),
+
+    /// Polling for `job_id` was stopped by `Client::shutdown`. The job itself keeps
+    /// running; resume with `Client::wait_for_job`.
+    #[error("polling for job {job_id} stopped by shutdown")]
+    Cancelled { job_id: String },
+
+    /// The file is bigger than the plan or storage backend allows: HTTP 413, a
+    /// `FILE_TOO_LARGE` code, or the signed URL rejecting it. Sizes are in bytes; `size`
+    /// is set for uploads, `limit` if the API reported it (`maxFileSize`).
+    #[error("file too large{}: {message}", size_detail(.size, .limit))]
+    FileTooLarge {
+        size: Option<u64>,
+        limit: Option<u64>,
+        message: String,
+    },
+
+    /// `upload` was given a file whose leading bytes don't match any known video or
+    /// audio container. `detected` is the MIME type it does look like, if recognized.
+    /// Set `ProcessOptions::skip_media_check` for formats without a signature.
+    #[error("{file_name} doesn't look like a video or audio file{}", detected_detail(.detected))]
+    UnsupportedMedia {
+        file_name: String,
+        detected: Option<String>,
+    },
+
+    /// Job status became `FAILED`. `reason` and `error_code` are the job's
+    /// `failureReason` and `errorCode`, if the API sent them.
+    #[error("job {job_id} failed ({status}){}", failure_detail(.reason, .error_code))]
+    JobFailed {
+        job_id: String,
+        status: String,
+        reason: Option<String>,
+        error_code: Option<String>,
+    },
+
+    /// The backend rejected a `list_jobs` cursor, usually because it expired: a 410, or
+    /// a 400 whose error code or message mentions the cursor. Restart the listing from
+    /// the first page.
+    #[error("list cursor rejected ({status_code}): {message}")]
+    InvalidCursor {
+        cursor: String,
+        status_code: u16,
+        message: String,
+    },
+
+    /// `list_all_jobs` found more jobs than its `max_jobs` cap.
+    #[error("more than {limit} jobs matched; results truncated")]
+    TooManyJobs { limit: usize },
 }
 
 pub type Result<T> = std::result::Result<T, FrameQueryError>;
+
+/// `": <reason> [<code>]"`, or whichever parts are present.
+fn failure_detail(reason: &Option<String>, error_code: &Option<String>) -> String {
+    match (reason, error_code) {
+        (Some(r), Some(c)) => format!(": {r} [{c}]"),
+        (Some(r), None) => format!(": {r}"),
+        (None, Some(c)) => format!(" [{c}]"),
+        (None, None) => String::new(),
+    }
+}
+
+/// `" (<size> bytes, limit <limit>)"`, or whichever parts are present.
+fn size_detail(size: &Option<u64>, limit: &Option<u64>) -> String {
+    match (size, limit) {
+        (Some(s), Some(l)) => format!(" ({s} bytes, limit {l})"),
+        (Some(s), None) => format!(" ({s} bytes)"),
+        (None, Some(l)) => format!(" (limit {l} bytes)"),
+        (None, None) => String::new(),
+    }
+}
+
+/// `" (detected <mime>)"`, or nothing.
+fn detected_detail(detected: &Option<String>) -> String {
+    detected
+        .as_ref()
+        .map_or(String::new(), |m| format!(" (detected {m})"))
+}
+
+impl FrameQueryError {
+    /// `Config` with a single problem.
+    pub(crate) fn config(message: impl Into<String>) -> Self {
+        Self::Config {
+            errors: vec![message.into()],
+        }
+    }
+
+    /// Fill in the upload's size on a `FileTooLarge` that came without one.
+    pub(crate) fn with_file_size(mut self, file_size: u64) -> Self {
+        if let Self::FileTooLarge { ref mut size, .. } = self {
+            size.get_or_insert(file_size);
+        }
+        self
+    }
+
+    /// `JobFailed` for a job whose status is a FAILED one.
+    pub(crate) fn job_failed(job: &Job) -> Self {
+        Self::JobFailed {
+            job_id: job.id.clone(),
+            status: job.status.clone(),
+            reason: job.failure_reason.clone(),
+            error_code: job.error_code.clone(),
+        }
+    }
+
+    /// Map a non-2xx response to a typed error. `text` is the raw response body.
+    pub(crate) fn from_response(status_code: u16, text: &str) -> Self {
+        let parsed_body: Option<serde_json::Value> = serde_json::from_str(text).ok();
+
+        let message = parsed_body
+            .as_ref()
+            .and_then(|b| b.get("error"))
+            .and_then(|e| e.as_str())
+            .unwrap_or(text)
+            .to_string();
+
+        let body_f64 = |key: &str| {
+            parsed_body
+                .as_ref()
+                .and_then(|b| b.get(key))
+                .and_then(|v| v.as_f64())
+        };
+        let code = parsed_body
+            .as_ref()
+            .and_then(|b| b.get("code").or_else(|| b.get("errorCode")))
+            .and_then(|c| c.as_str());
+
+        match status_code {
+            402 | 403 if status_code == 402 || code == Some("QUOTA_EXCEEDED") => {
+                Self::QuotaExceeded {
+                    message,
+                    needed_hours: body_f64("neededHours"),
+                    available_hours: body_f64("availableHours"),
+                }
+            }
+            _ if status_code == 413 || code == Some("FILE_TOO_LARGE") => Self::FileTooLarge {
+                size: body_f64("fileSize").map(|n| n as u64),
+                limit: body_f64("maxFileSize").map(|n| n as u64),
+                message,
+            },
+            401 => Self::Authentication { message },
+            403 => Self::PermissionDenied { message },
+            404 => Self::NotFound { message },
+            429 => Self::RateLimit {
+                message,
+                retry_after: body_f64("retryAfter"),
+            },
+            _ => Self::Api {
+                status_code,
+                message,
+                body: parsed_body,
+            },
+        }
+    }
+
+    /// `true` for errors worth retrying: 5xx, 429, 408, timeouts, and connection failures.
+    /// Mirrors what the client's own retry loop retries by default.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimit { .. } => true,
+            Self::Api { status_code, .. } => *status_code >= 500 || *status_code == 408,
+            Self::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// How long the server asked us to wait before retrying: a 429's `retryAfter` (or
+    /// `Retry-After` header), or a `retryAfter` in a 503 body. For scheduling your own
+    /// retry once the client's are exhausted.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        let secs = match self {
+            Self::RateLimit { retry_after, .. } => *retry_after,
+            Self::Api {
+                status_code: 503,
+                body: Some(body),
+                ..
+            } => body.get("retryAfter").and_then(|v| v.as_f64()),
+            _ => None,
+        }?;
+        std::time::Duration::try_from_secs_f64(secs.max(0.0)).ok()
+    }
+
+    /// `true` if the HTTP request timed out, or polling exceeded its timeout.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout(_) => true,
+            Self::Http(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// `true` if the connection couldn't be established (DNS, refused, TLS handshake).
+    pub fn is_connect(&self) -> bool {
+        matches!(self, Self::Http(e) if e.is_connect())
+    }
+
+    /// `true` if the response body couldn't be decoded.
+    pub fn is_decode(&self) -> bool {
+        matches!(self, Self::Decode { .. }) || matches!(self, Self::Http(e) if e.is_decode())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::client::Client;
+use crate::errors::{FrameQueryError, Result};
+use crate::models::{ProcessOptions, ProcessingResult};
+
+/// A running job that is cancelled if dropped before it finishes. Returned by
+/// [`Client::start`].
+///
+/// Drop can't await, so the cancel is a best-effort request on a detached tokio task.
+/// It is skipped if no tokio runtime is running at drop time. Use [`forget`](Self::forget)
+/// to keep the job running regardless.
+#[derive(Debug)]
+pub struct JobHandle {
+    client: Client,
+    job_id: String,
+    cancel_on_drop: bool,
+}
+
+impl JobHandle {
+    pub(crate) fn new(client: Client, job_id: String) -> Self {
+        Self {
+            client,
+            job_id,
+            cancel_on_drop: true,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.job_id
+    }
+
+    /// Turn cancel-on-drop on or off. Default: on.
+    pub fn cancel_on_drop(mut self, enabled: bool) -> Self {
+        self.cancel_on_drop = enabled;
+        self
+    }
+
+    /// Poll until the job completes, like [`Client::wait_for_job`]. Once the job has
+    /// finished or failed, dropping the handle no longer cancels it. Nor does it after
+    /// `Cancelled` from [`Client::shutdown`], which leaves remote jobs running. After
+    /// other errors (e.g. `Timeout`) the job may still be running and the handle stays
+    /// armed.
+    pub async fn wait(&mut self, opts: &ProcessOptions) -> Result<ProcessingResult> {
+        let result = self.client.wait_for_job(&self.job_id, opts).await;
+        if matches!(
+            result,
+            Ok(_) | Err(FrameQueryError::JobFailed { .. } | FrameQueryError::Cancelled { .. })
+        ) {
+            self.cancel_on_drop = false;
+        }
+        result
+    }
+
+    /// Disarm the handle and return the job id. The job keeps running.
+    pub fn forget(mut self) -> String {
+        self.cancel_on_drop = false;
+        std::mem::take(&mut self.job_id)
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if !self.cancel_on_drop {
+            return;
+        }
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let client = self.client.clone();
+            let job_id = std::mem::take(&mut self.job_id);
+            runtime.spawn(async move {
+                let _ = client.cancel_job(&job_id).await;
+            });
+        }
+    }
+}
@@ -20,11 +20,20 @@
 
 mod client;
 mod errors;
+mod handle;
+mod metrics;
 mod models;
+mod shutdown;
+mod transport;
 
-pub use client::{Client, ClientBuilder};
+pub use client::{AuthScheme, Client, ClientBuilder};
 pub use errors::{FrameQueryError, Result};
+pub use handle::JobHandle;
+pub use metrics::Metrics;
 pub use models::{
-    AudioTrack, AudioTrackTranscript, BatchClip, BatchJobEntry, BatchOptions, BatchResult, Job,
-    JobPage, ProcessOptions, ProcessingResult, Quota, Scene, TranscriptSegment,
+    ArtifactKind, AudioTrack, AudioTrackTranscript, BatchClip, BatchJobEntry, BatchOptions,
+    BatchResult, CancelSummary, Job, JobDelta, JobPage, JobStatus, ListJobsOptions, Plan,
+    ProcessEvent, ProcessOptions, ProcessingResult, ProgressUpdate, Quota, RateLimitInfo, Scene,
+    SceneSpan, SceneStatus, TranscriptSegment, VideoMetadata,
 };
+pub use transport::Transport;
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+/// Observability hooks, so SDK activity can be exported to Prometheus, StatsD, etc.
+/// without the SDK depending on a metrics crate. Install with
+/// [`ClientBuilder::metrics`](crate::ClientBuilder::metrics).
+///
+/// Every method defaults to a no-op; implement only the ones you need. Callbacks run
+/// inline on the request path, so keep them cheap.
+///
+/// `endpoint` is `"<METHOD> <path>"` with ids replaced by placeholders, e.g.
+/// `"GET /jobs/{job_id}"`, so it is safe to use as a low-cardinality label. Artifact
+/// downloads, whose URLs aren't API paths, use e.g. `"GET {report_artifact}"`.
+pub trait Metrics: Send + Sync {
+    /// One HTTP attempt against the API finished. `status` is `None` for network errors.
+    fn on_request(&self, endpoint: &str, status: Option<u16>, elapsed: Duration) {
+        let _ = (endpoint, status, elapsed);
+    }
+
+    /// An API request is about to be retried.
+    fn on_retry(&self, endpoint: &str) {
+        let _ = endpoint;
+    }
+
+    /// A file's bytes were uploaded to its signed URL.
+    fn on_upload(&self, bytes: u64) {
+        let _ = bytes;
+    }
+
+    /// Polling saw the job reach a terminal `status`, `elapsed` after polling started.
+    fn on_job_finished(&self, status: &str, elapsed: Duration) {
+        let _ = (status, elapsed);
+    }
+}
+
+/// The default: records nothing.
+pub(crate) struct NoopMetrics;
+
+impl Metrics for NoopMetrics {}
+
+/// `"<METHOD> <path>"` with the query string dropped and ids templated.
+pub(crate) fn endpoint_label(method: &str, path: &str) -> String {
+    let path = path.split('?').next().unwrap_or(path);
+    let mut label = String::with_capacity(method.len() + path.len() + 1);
+    label.push_str(method);
+    label.push(' ');
+
+    let mut prev = "";
+    for (i, seg) in path.split('/').enumerate() {
+        if i > 0 {
+            label.push('/');
+        }
+        label.push_str(match prev {
+            "jobs" if seg != "from-url" && seg != "batch" => "{job_id}",
+            "batches" => "{batch_id}",
+            "audio-tracks" => "{track}",
+            _ => seg,
+        });
+        prev = seg;
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_label_templates_ids_and_drops_query() {
+        assert_eq!(endpoint_label("GET", "/jobs/abc123"), "GET /jobs/{job_id}");
+        assert_eq!(
+            endpoint_label("GET", "/jobs/abc/audio-tracks/2"),
+            "GET /jobs/{job_id}/audio-tracks/{track}"
+        );
+        assert_eq!(
+            endpoint_label("POST", "/jobs/from-url"),
+            "POST /jobs/from-url"
+        );
+        assert_eq!(endpoint_label("POST", "/jobs/batch"), "POST /jobs/batch");
+        assert_eq!(
+            endpoint_label("GET", "/batches/b1"),
+            "GET /batches/{batch_id}"
+        );
+        assert_eq!(endpoint_label("GET", "/jobs?limit=10"), "GET /jobs");
+    }
+}
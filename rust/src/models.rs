@@ -1,5 +1,11 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
+/// Most marks [`ProcessingResult::scenes_every`] returns.
+const MAX_SCENE_MARKS: usize = 10_000;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Scene {
     pub description: String,
@@ -13,6 +19,18 @@ pub struct Scene {
     pub objects: Vec<String>,
 }
 
+impl Scene {
+    /// `objects` with duplicates removed, in first-seen order.
+    pub fn unique_objects(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.objects
+            .iter()
+            .map(String::as_str)
+            .filter(|o| seen.insert(*o))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TranscriptSegment {
     #[serde(rename = "StartTime")]
@@ -23,6 +41,10 @@ pub struct TranscriptSegment {
 
     #[serde(rename = "Text")]
     pub text: String,
+
+    /// Spoken language of this segment (e.g. `"en"`), if the API reports it per segment.
+    #[serde(default, rename = "Language", skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// An additional audio track attached to a job.
@@ -34,7 +56,11 @@ pub struct AudioTrack {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 
-    #[serde(default, rename = "downloadToken", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        rename = "downloadToken",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub download_token: Option<String>,
 
     #[serde(default, rename = "syncMode", skip_serializing_if = "Option::is_none")]
@@ -46,7 +72,11 @@ pub struct AudioTrack {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
 
-    #[serde(default, rename = "perChannelTranscription", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        rename = "perChannelTranscription",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub per_channel_transcription: Option<bool>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -86,14 +116,249 @@ pub struct ProcessingResult {
     pub filename: String,
     /// Video length in seconds.
     pub duration: f64,
+    /// Sorted by `end_time`.
     pub scenes: Vec<Scene>,
+    /// Sorted by `start_time`.
     pub transcript: Vec<TranscriptSegment>,
+    /// Technical metadata of the source video.
+    pub metadata: VideoMetadata,
+    /// `true` if the backend deduplicated this upload and served a cached result.
+    /// Useful for billing reconciliation.
+    pub from_cache: bool,
     /// ISO 8601.
     pub created_at: String,
+    /// Spoken language the backend auto-detected (e.g. `"en"`), if reported.
+    pub detected_language: Option<String>,
+    /// Processing hours charged for this job (`billedHours`), for attributing spend.
+    /// `None` if the API didn't report it, e.g. for a result served from cache.
+    pub billed_hours: Option<f64>,
+    /// `true` if the response had a `processedData.scenes` field, i.e. scene detection
+    /// ran. See [`scene_status`](Self::scene_status).
+    pub scenes_attempted: bool,
     /// Full API response JSON.
     pub raw: serde_json::Value,
 }
 
+/// Technical metadata of a processed video. Fields the API didn't report are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VideoMetadata {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Frames per second.
+    pub fps: Option<f64>,
+    /// e.g. "h264".
+    pub codec: Option<String>,
+    pub size_bytes: Option<u64>,
+}
+
+impl ProcessingResult {
+    /// `created_at` parsed as UTC. `None` if it's empty or not RFC 3339.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// Scenes paired with derived start times. The API only sends each scene's end
+    /// timestamp, so a scene is taken to start where the previous one ended (the first
+    /// starts at 0).
+    pub fn scenes_with_spans(&self) -> Vec<SceneSpan<'_>> {
+        let mut start = 0.0_f64;
+        self.scenes
+            .iter()
+            .map(|scene| {
+                let span = SceneSpan {
+                    start_time: start.min(scene.end_time),
+                    end_time: scene.end_time,
+                    scene,
+                };
+                start = scene.end_time;
+                span
+            })
+            .collect()
+    }
+
+    /// Whether scene detection ran and found anything. Distinguishes a `*_NO_SCENES`
+    /// status or a missing `scenes` field (detection never ran, e.g. transcript-only
+    /// processing) from a detection pass that returned an empty list.
+    pub fn scene_status(&self) -> SceneStatus {
+        if self.status.ends_with("NO_SCENES") || !self.scenes_attempted {
+            SceneStatus::NotAttempted
+        } else if self.scenes.is_empty() {
+            SceneStatus::NoneFound
+        } else {
+            SceneStatus::Detected
+        }
+    }
+
+    /// The transcript with consecutive segments joined when the silence between them is
+    /// under `max_gap_secs`. Merged text is space-separated and the span covers both
+    /// segments. Segments separated by a larger gap stay separate.
+    pub fn merged_transcript(&self, max_gap_secs: f64) -> Vec<TranscriptSegment> {
+        let mut merged: Vec<TranscriptSegment> = Vec::with_capacity(self.transcript.len());
+        for seg in &self.transcript {
+            match merged.last_mut() {
+                Some(prev) if seg.start_time - prev.end_time < max_gap_secs => {
+                    let text = seg.text.trim();
+                    if !text.is_empty() {
+                        if !prev.text.is_empty() {
+                            prev.text.push(' ');
+                        }
+                        prev.text.push_str(text);
+                    }
+                    prev.end_time = prev.end_time.max(seg.end_time);
+                }
+                _ => merged.push(TranscriptSegment {
+                    text: seg.text.trim().to_string(),
+                    ..seg.clone()
+                }),
+            }
+        }
+        merged
+    }
+
+    /// Lowercased word → start times of the transcript segments containing it, for
+    /// click-to-seek search. Leading and trailing punctuation is stripped, so "Hello,"
+    /// and "hello" are the same word; inner apostrophes and hyphens are kept. Each
+    /// segment appears at most once per word, in transcript order.
+    pub fn build_word_index(&self) -> HashMap<String, Vec<f64>> {
+        let mut index: HashMap<String, Vec<f64>> = HashMap::new();
+        for seg in &self.transcript {
+            let mut seen = HashSet::new();
+            for word in seg.text.split_whitespace() {
+                let word = word
+                    .trim_matches(|c: char| !c.is_alphanumeric())
+                    .to_lowercase();
+                if !word.is_empty() && seen.insert(word.clone()) {
+                    index.entry(word).or_default().push(seg.start_time);
+                }
+            }
+        }
+        index
+    }
+
+    /// Fraction of the video (0.0-1.0) covered by transcript segments. Overlapping
+    /// segments count once. 0.0 if `duration` is 0.
+    pub fn transcript_coverage(&self) -> f64 {
+        coverage(
+            self.transcript.iter().map(|s| (s.start_time, s.end_time)),
+            self.duration,
+        )
+    }
+
+    /// Fraction of the video (0.0-1.0) covered by detected scenes, using the spans from
+    /// [`scenes_with_spans`](Self::scenes_with_spans). 0.0 if `duration` is 0.
+    pub fn scene_coverage(&self) -> f64 {
+        coverage(
+            self.scenes_with_spans()
+                .iter()
+                .map(|s| (s.start_time, s.end_time)),
+            self.duration,
+        )
+    }
+
+    /// `count` distinct scenes spread evenly over the video, in order, e.g. for a
+    /// filmstrip. Each is the scene on screen at the middle of one of `count` equal
+    /// slices of the video, moved to a neighbour where several slices land in the same
+    /// scene. All scenes if there are no more than `count`.
+    pub fn sample_scenes(&self, count: usize) -> Vec<&Scene> {
+        if count >= self.scenes.len() {
+            return self.scenes.iter().collect();
+        }
+        let duration = self.scene_timeline_len();
+        let mut picked: Vec<usize> = Vec::with_capacity(count);
+        for i in 0..count {
+            let mark = duration * (i as f64 + 0.5) / count as f64;
+            // Leave room after this pick for the ones still to come.
+            let min = picked.last().map_or(0, |&p| p + 1);
+            let max = self.scenes.len() - (count - i);
+            picked.push(self.scene_index_at(mark).clamp(min, max));
+        }
+        picked.into_iter().map(|i| &self.scenes[i]).collect()
+    }
+
+    /// The scene on screen at 0s, `interval_secs`, `2 * interval_secs`, ... up to the end
+    /// of the video, one per mark, e.g. for a thumbnail every 10 seconds. A long scene
+    /// repeats for each mark it spans. Empty if there are no scenes or `interval_secs`
+    /// isn't positive. At most 10,000 marks: a smaller interval is widened to fit.
+    pub fn scenes_every(&self, interval_secs: f64) -> Vec<&Scene> {
+        if self.scenes.is_empty() || interval_secs.is_nan() || interval_secs <= 0.0 {
+            return Vec::new();
+        }
+        let duration = self.scene_timeline_len();
+        let interval_secs = interval_secs.max(duration / MAX_SCENE_MARKS as f64);
+        let mut marks = Vec::new();
+        let mut mark = 0.0;
+        while (mark < duration || marks.is_empty()) && marks.len() < MAX_SCENE_MARKS {
+            marks.push(&self.scenes[self.scene_index_at(mark)]);
+            mark = marks.len() as f64 * interval_secs;
+        }
+        marks
+    }
+
+    /// `duration`, or the last scene's end if the API didn't report a duration.
+    fn scene_timeline_len(&self) -> f64 {
+        if self.duration > 0.0 {
+            self.duration
+        } else {
+            self.scenes.last().map_or(0.0, |s| s.end_time)
+        }
+    }
+
+    /// Index of the scene on screen at `secs`: the first ending at or after it, else the
+    /// last. `scenes` must be non-empty.
+    fn scene_index_at(&self, secs: f64) -> usize {
+        self.scenes
+            .partition_point(|s| s.end_time < secs)
+            .min(self.scenes.len() - 1)
+    }
+}
+
+/// Length of the union of `intervals` within `[0, duration]`, as a fraction of `duration`.
+fn coverage(intervals: impl Iterator<Item = (f64, f64)>, duration: f64) -> f64 {
+    if duration <= 0.0 || duration.is_nan() {
+        return 0.0;
+    }
+    let mut spans: Vec<(f64, f64)> = intervals
+        .map(|(start, end)| (start.max(0.0), end.min(duration)))
+        .filter(|(start, end)| end > start)
+        .collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut covered = 0.0;
+    let mut current: Option<(f64, f64)> = None;
+    for (start, end) in spans {
+        current = match current {
+            Some((cs, ce)) if start <= ce => Some((cs, ce.max(end))),
+            Some((cs, ce)) => {
+                covered += ce - cs;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((cs, ce)) = current {
+        covered += ce - cs;
+    }
+    (covered / duration).min(1.0)
+}
+
+/// A [`Scene`] with a start time derived by [`ProcessingResult::scenes_with_spans`].
+#[derive(Debug, Clone, Copy)]
+pub struct SceneSpan<'a> {
+    /// Seconds from video start.
+    pub start_time: f64,
+    /// Seconds from video start. Same as `scene.end_time`.
+    pub end_time: f64,
+    pub scene: &'a Scene,
+}
+
+impl SceneSpan<'_> {
+    /// `end_time - start_time`, in seconds.
+    pub fn duration(&self) -> f64 {
+        self.end_time - self.start_time
+    }
+}
+
 /// Current state of a job. Check `status` or use the `is_*` helpers.
 #[derive(Debug, Clone)]
 pub struct Job {
@@ -110,6 +375,24 @@ pub struct Job {
     pub audio_tracks_completed: Option<u32>,
     /// Names/labels of the audio tracks.
     pub audio_track_names: Vec<String>,
+    /// `true` if the backend deduplicated this upload and served a cached result.
+    pub from_cache: bool,
+    /// Why a failed job failed (`failureReason`), if the API said.
+    pub failure_reason: Option<String>,
+    /// Machine-readable failure code (`errorCode`), e.g. for telling an unsupported
+    /// codec from quota running out mid-job.
+    pub error_code: Option<String>,
+    /// Tags the job was created with (`ProcessOptions::tags`).
+    pub tags: Vec<String>,
+    /// Processing hours charged (`billedHours`). Usually only reported once the job is
+    /// terminal; `None` before then.
+    pub billed_hours: Option<f64>,
+    /// Server-reported progress (`progress`), as sent: a 0-1 fraction or a 0-100
+    /// percentage. See [`progress_fraction`](Self::progress_fraction).
+    pub progress: Option<f64>,
+    /// Base64 digest sent with the upload (`Content-MD5` or `x-amz-checksum-sha256`),
+    /// when the API asked for one. Only set on the `Job` returned by `Client::upload`.
+    pub upload_checksum: Option<String>,
     /// Full API response JSON.
     pub raw: serde_json::Value,
 }
@@ -122,19 +405,45 @@ impl Job {
 
     /// VISION_COMPLETED or VIDEO_COMPLETED_NO_SCENES.
     pub fn is_complete(&self) -> bool {
-        matches!(
-            self.status.as_str(),
-            "VISION_COMPLETED" | "VIDEO_COMPLETED_NO_SCENES"
-        )
+        status_is_complete(&self.status)
     }
 
     /// Any status containing "FAILED" (e.g. FAILED_FETCH, INGEST_FAILED_TRANSCODE).
     pub fn is_failed(&self) -> bool {
-        self.status.contains("FAILED")
+        status_is_failed(&self.status)
+    }
+
+    /// `created_at` parsed as UTC. `None` if it's empty (e.g. on a `Job` fresh from
+    /// `upload`) or not RFC 3339.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// Rough completion, 0.0-1.0, for progress bars when `eta_seconds` is absent.
+    ///
+    /// Uses the response's `progress` field if present (a 0-1 fraction or a 0-100
+    /// percentage), else a coarse guess from the status. `None` for failed jobs and
+    /// statuses this SDK doesn't know.
+    pub fn progress_fraction(&self) -> Option<f64> {
+        if let Some(p) = self.progress {
+            let p = if p > 1.0 { p / 100.0 } else { p };
+            return Some(p.clamp(0.0, 1.0));
+        }
+        if self.is_complete() {
+            return Some(1.0);
+        }
+        match self.status.as_str() {
+            "PENDING_UPLOAD" => Some(0.0),
+            "PENDING_ORCHESTRATION" => Some(0.1),
+            "PROCESSING" => Some(0.5),
+            _ => None,
+        }
     }
 
     /// Parse `processedData` from the raw response into a [`ProcessingResult`].
-    /// Returns `None` if the job isn't complete or has no processed data.
+    /// Returns `None` if the job isn't complete or has no processed data. Always lenient;
+    /// use [`Client::job_result`](crate::Client::job_result) to honor `strict_parsing`.
     pub fn result(&self) -> Option<ProcessingResult> {
         if !self.is_complete() {
             return None;
@@ -142,9 +451,135 @@ impl Job {
         self.raw.get("processedData")?;
         Some(processing_result_from_value(self.raw.clone()))
     }
+
+    /// What changed from `previous` to this snapshot of the same job. Compares the
+    /// typed fields only, never `raw`.
+    pub fn changed_since(&self, previous: &Job) -> JobDelta {
+        JobDelta {
+            status: (self.status != previous.status)
+                .then(|| (previous.status.clone(), self.status.clone())),
+            eta_seconds: (self.eta_seconds != previous.eta_seconds)
+                .then_some((previous.eta_seconds, self.eta_seconds)),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Differences between two snapshots of a job, as `(previous, current)` pairs. See
+/// [`Job::changed_since`]. Displays as e.g. `status PROCESSING → VISION_COMPLETED`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JobDelta {
+    pub status: Option<(String, String)>,
+    pub eta_seconds: Option<(Option<f64>, Option<f64>)>,
+}
+
+impl JobDelta {
+    /// `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.eta_seconds.is_none()
+    }
+}
+
+impl std::fmt::Display for JobDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let eta = |eta: &Option<f64>| eta.map_or("none".to_string(), |s| format!("{s:.0}s"));
+        let mut parts = Vec::new();
+        if let Some((ref from, ref to)) = self.status {
+            parts.push(format!("status {from} → {to}"));
+        }
+        if let Some((ref from, ref to)) = self.eta_seconds {
+            parts.push(format!("eta {} → {}", eta(from), eta(to)));
+        }
+        if parts.is_empty() {
+            return f.write_str("no changes");
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// What `ProcessOptions::on_update` receives on each poll.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressUpdate<'a> {
+    pub job: &'a Job,
+    /// When the job should finish, from the server's `eta_seconds` readings smoothed
+    /// over the polls so far, so one jittery ETA doesn't jerk a countdown around.
+    /// `None` until the server has sent an ETA.
+    pub estimated_completion: Option<std::time::Instant>,
+}
+
+/// Outcome of scene detection for a [`ProcessingResult`]. See
+/// [`ProcessingResult::scene_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SceneStatus {
+    /// Detection ran and found at least one scene.
+    Detected,
+    /// Detection ran but returned no scenes. Worth alerting on for footage that should
+    /// have them.
+    NoneFound,
+    /// Detection didn't run for this job.
+    NotAttempted,
+}
+
+/// Just a job's status string, from `Client::get_status`. Cheaper than a full [`Job`]
+/// for tight polling loops.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct JobStatus {
+    pub status: String,
+}
+
+impl JobStatus {
+    /// Same as [`Job::is_terminal`].
+    pub fn is_terminal(&self) -> bool {
+        self.is_complete() || self.is_failed()
+    }
+
+    /// Same as [`Job::is_complete`].
+    pub fn is_complete(&self) -> bool {
+        status_is_complete(&self.status)
+    }
+
+    /// Same as [`Job::is_failed`].
+    pub fn is_failed(&self) -> bool {
+        status_is_failed(&self.status)
+    }
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.status)
+    }
+}
+
+fn status_is_complete(status: &str) -> bool {
+    matches!(status, "VISION_COMPLETED" | "VIDEO_COMPLETED_NO_SCENES")
+}
+
+fn status_is_failed(status: &str) -> bool {
+    status.contains("FAILED")
+}
+
+/// A downloadable file produced by processing. See `Client::download_artifact`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactKind {
+    /// Thumbnail sprite sheet.
+    Thumbnail,
+    /// JSON processing report.
+    Report,
+    /// The normalized (transcoded) video.
+    Normalized,
+}
+
+impl ArtifactKind {
+    /// Key under the job payload's `artifacts` object.
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Self::Thumbnail => "thumbnail",
+            Self::Report => "report",
+            Self::Normalized => "normalized",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Quota {
     /// "free", "starter", "pro", or "enterprise".
     #[serde(rename = "currentPlan")]
@@ -163,11 +598,122 @@ pub struct Quota {
     pub reset_date: Option<String>,
 }
 
+impl Quota {
+    /// `included_hours + credits_balance_hours`.
+    pub fn total_available_hours(&self) -> f64 {
+        self.included_hours + self.credits_balance_hours
+    }
+
+    /// `true` if no included hours or credits are left.
+    pub fn is_exhausted(&self) -> bool {
+        self.total_available_hours() <= 0.0
+    }
+
+    /// [`plan`](Self::plan) as a [`Plan`], for matching without string comparisons.
+    pub fn plan_enum(&self) -> Plan {
+        Plan::from(self.plan.as_str())
+    }
+}
+
+/// A billing plan. See [`Quota::plan_enum`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Plan {
+    Free,
+    Starter,
+    Pro,
+    Enterprise,
+    /// A plan this SDK version doesn't know about, as sent by the API.
+    Other(String),
+}
+
+/// Case-insensitive; anything unrecognized becomes [`Plan::Other`].
+impl From<&str> for Plan {
+    fn from(plan: &str) -> Self {
+        match plan.to_ascii_lowercase().as_str() {
+            "free" => Self::Free,
+            "starter" => Self::Starter,
+            "pro" => Self::Pro,
+            "enterprise" => Self::Enterprise,
+            _ => Self::Other(plan.to_string()),
+        }
+    }
+}
+
+/// e.g. `pro plan: 10.0h included + 2.5h credits (resets 2025-02-01T00:00:00Z)`.
+impl std::fmt::Display for Quota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} plan: {:.1}h included + {:.1}h credits",
+            self.plan, self.included_hours, self.credits_balance_hours
+        )?;
+        if let Some(ref date) = self.reset_date {
+            write!(f, " (resets {date})")?;
+        }
+        Ok(())
+    }
+}
+
+/// `X-RateLimit-*` headers from an API response. See `Client::last_rate_limit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// `X-RateLimit-Limit`: requests allowed per window.
+    pub limit: Option<u64>,
+    /// `X-RateLimit-Remaining`: requests left in the current window.
+    pub remaining: Option<u64>,
+    /// `X-RateLimit-Reset`: when the window resets, as sent by the server.
+    pub reset: Option<u64>,
+}
+
+impl RateLimitInfo {
+    /// `None` if the response carried none of the headers.
+    pub(crate) fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let num = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+        };
+        let info = Self {
+            limit: num("x-ratelimit-limit"),
+            remaining: num("x-ratelimit-remaining"),
+            reset: num("x-ratelimit-reset"),
+        };
+        (info.limit.is_some() || info.remaining.is_some() || info.reset.is_some()).then_some(info)
+    }
+}
+
+/// Filters for `Client::list_jobs_with`. Unset fields aren't sent.
+#[derive(Debug, Clone, Default)]
+pub struct ListJobsOptions {
+    /// Page size.
+    pub limit: Option<u32>,
+    /// `next_cursor` from the previous page.
+    pub cursor: Option<String>,
+    pub status: Option<String>,
+    /// Only jobs created at or after this time. ISO 8601.
+    pub created_after: Option<String>,
+    /// Only jobs created before this time. ISO 8601.
+    pub created_before: Option<String>,
+    /// Return only these job fields (API names, e.g. `jobId`, `status`,
+    /// `originalFilename`), for smaller pages in large scans. `Job` fields left out are
+    /// empty/`None`, and `raw` holds just the requested ones. Sent as `fields=a,b,c`.
+    pub fields: Option<Vec<String>>,
+    /// Only jobs carrying all of these tags (see `ProcessOptions::tags`). Sent as
+    /// `tags=a,b`, so tags containing commas can't be filtered on.
+    pub tags: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct JobPage {
     pub jobs: Vec<Job>,
     /// Pass to `list_jobs` for the next page. `None` means no more results.
+    ///
+    /// Opaque plain string, safe to persist and hand to `Client::list_jobs_from` to
+    /// resume later. Cursors may expire; a stale one fails with `InvalidCursor`.
     pub next_cursor: Option<String>,
+    /// Total jobs matching the filters across all pages, if the API reported it.
+    pub total: Option<u64>,
 }
 
 impl JobPage {
@@ -177,16 +723,47 @@ impl JobPage {
     }
 }
 
-/// Polling config for `process` / `process_url`.
+/// Polling config for `process` / `process_url`. Cheap to clone; callbacks are shared.
+#[derive(Clone)]
 pub struct ProcessOptions {
     /// Default: 5s.
     pub poll_interval: std::time::Duration,
-    /// Default: 24h.
+    /// Wait this long before the first status check; a new job won't be done for a
+    /// while anyway. Default: 0.
+    pub initial_delay: std::time::Duration,
+    /// Polling budget. Default: 24h.
     pub timeout: std::time::Duration,
-    /// Called on each poll iteration with the current `Job`.
+    /// Budget for the whole `process` / `process_url` call: create + upload + poll.
+    /// Whichever of this and `timeout` runs out first ends the call with `Timeout`.
+    /// Default: `None` (no overall limit).
+    pub total_timeout: Option<std::time::Duration>,
+    /// Give up with `MaxPollsExceeded` after this many status checks, regardless of
+    /// elapsed time. The checks retried because a just-created job 404'd count too.
+    /// Default: `None` (only `timeout` applies).
+    pub max_polls: Option<u32>,
+    /// Called on each poll iteration with the current `Job`. A panic in the callback is
+    /// caught and swallowed: the panic hook still reports it, `debug_log` also logs it,
+    /// and polling carries on, so a buggy progress bar can't abandon a running job.
+    #[allow(clippy::type_complexity)]
+    pub on_progress: Option<Arc<dyn Fn(&Job) + Send + Sync>>,
+    /// Like `on_progress`, but also gets a smoothed completion estimate; see
+    /// [`ProgressUpdate`]. Both callbacks run if both are set. Panics are caught, as for
+    /// `on_progress`. Default: `None`.
     #[allow(clippy::type_complexity)]
-    pub on_progress: Option<Box<dyn Fn(&Job) + Send>>,
-    /// Optional webhook URL to receive callbacks when job completes.
+    pub on_update: Option<Arc<dyn Fn(&ProgressUpdate<'_>) + Send + Sync>>,
+    /// `process` / `process_url` only: called with the job id once the job is created
+    /// (and uploaded), before polling starts. Persist it, and if polling later fails,
+    /// resume with `Client::wait_for_job` instead of uploading again. Called again for
+    /// each `retry_source_fetch` resubmission. Panics are caught, as for `on_progress`.
+    /// Default: `None`.
+    #[allow(clippy::type_complexity)]
+    pub on_job_created: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    /// `wait_until` only: stop polling as soon as this returns `true` for the current
+    /// `Job`, e.g. to hand off once processing has started. Default: wait for completion.
+    #[allow(clippy::type_complexity)]
+    pub until: Option<Arc<dyn Fn(&Job) -> bool + Send + Sync>>,
+    /// Webhook the backend calls when the job completes, sent as `callbackUrl`. Must be
+    /// `https`; job creation fails with `Config` otherwise.
     pub callback_url: Option<String>,
     /// Optional processing mode (e.g. "fast", "quality").
     pub processing_mode: Option<String>,
@@ -194,22 +771,80 @@ pub struct ProcessOptions {
     pub idempotency_key: Option<String>,
     /// Additional audio tracks to process alongside the video.
     pub audio_tracks: Option<Vec<AudioTrack>>,
+    /// `process_url` only: filename to record for the job. Default: derived from the
+    /// URL path, or "video.mp4" if the path has no extension.
+    pub file_name: Option<String>,
+    /// `process_url` only: headers the backend should send when fetching the source URL
+    /// (e.g. `Authorization` for private sources).
+    pub source_headers: Option<HashMap<String, String>>,
+    /// Poll with `GET /jobs/{id}?light=true`, a status-only view without
+    /// `processedData`, and fetch the full job once when it completes. Saves bandwidth on
+    /// long jobs. A `Job` seen before completion (in `on_progress`, or returned by
+    /// `wait_until`) is the lightweight view. Default: `false`.
+    pub light_polling: bool,
+    /// Extra top-level fields for the create-job body, for API parameters this SDK
+    /// doesn't model yet (e.g. `{"priority": "high"}`). Must be a JSON object. Never
+    /// overrides a field the SDK sets itself, such as `fileName`. Default: `None`.
+    pub extra_fields: Option<serde_json::Value>,
+    /// For `process_url`: if the job fails because the source URL couldn't be fetched
+    /// (status `FAILED_FETCH`, or code `SOURCE_NOT_REACHABLE` / `SOURCE_NOT_FOUND`),
+    /// submit it again up to this many times, with backoff. Helps with CDN objects that
+    /// haven't propagated yet. Leave `idempotency_key` unset with this, or the API may
+    /// hand back the same failed job. Default: 0.
+    pub retry_source_fetch: u32,
+    /// `upload` / `process` only: skip checking that the file starts like a video or
+    /// audio container, which otherwise fails fast with `UnsupportedMedia` instead of
+    /// spending a job on e.g. a `.txt`. Set it for valid formats with no recognizable
+    /// signature. Default: `false`.
+    pub skip_media_check: bool,
+    /// Labels stored on the job (`tags` in the create body), e.g. the end customer it
+    /// belongs to. Returned as `Job::tags` and filterable with `ListJobsOptions::tags`.
+    /// Default: none.
+    pub tags: Vec<String>,
 }
 
 impl Default for ProcessOptions {
     fn default() -> Self {
         Self {
             poll_interval: std::time::Duration::from_secs(5),
+            initial_delay: std::time::Duration::ZERO,
             timeout: std::time::Duration::from_secs(24 * 60 * 60),
+            total_timeout: None,
+            max_polls: None,
             on_progress: None,
+            on_update: None,
+            on_job_created: None,
+            until: None,
             callback_url: None,
             processing_mode: None,
             idempotency_key: None,
             audio_tracks: None,
+            file_name: None,
+            source_headers: None,
+            light_polling: false,
+            extra_fields: None,
+            retry_source_fetch: 0,
+            skip_media_check: false,
+            tags: Vec::new(),
         }
     }
 }
 
+/// Emitted by `Client::process_with_events`. The stream ends after `Completed` or `Failed`.
+#[derive(Debug, Clone)]
+pub enum ProcessEvent {
+    /// Upload progress in bytes: `sent: 0` first, then as the file goes out in 64 KiB
+    /// chunks, then `sent == total` once it's uploaded. A retried upload starts over
+    /// from 0.
+    UploadProgress { sent: u64, total: u64 },
+    /// Job state from one poll.
+    JobStatus(Job),
+    /// The job completed.
+    Completed(ProcessingResult),
+    /// The upload, polling, or job itself failed, or polling timed out.
+    Failed(String),
+}
+
 /// A single clip in a batch request.
 pub struct BatchClip {
     pub source_url: String,
@@ -254,6 +889,16 @@ pub struct BatchJobEntry {
     pub status: String,
 }
 
+/// Outcome of `Client::cancel_jobs_where`.
+#[derive(Debug, Default)]
+pub struct CancelSummary {
+    /// Ids of the jobs cancelled.
+    pub cancelled: Vec<String>,
+    /// Jobs whose cancel failed, with why; e.g. a 409 for one that finished in the
+    /// meantime.
+    pub failed: Vec<(String, crate::errors::FrameQueryError)>,
+}
+
 // ---------------------------------------------------------------------------
 // Internal deserialization helpers (not part of the public API surface)
 // ---------------------------------------------------------------------------
@@ -273,23 +918,22 @@ pub(crate) struct CreateJobData {
     #[serde(default, rename = "expiresInSeconds")]
     #[allow(dead_code)]
     pub expires_in_seconds: Option<u64>,
+    /// `PUT` (raw body) if absent; `POST` means a multipart form upload.
     #[serde(default, rename = "uploadMethod")]
-    #[allow(dead_code)]
     pub upload_method: Option<String>,
+    /// Form fields to send ahead of the file for a `POST` upload, e.g. an S3 policy.
+    #[serde(default, rename = "uploadFields")]
+    pub upload_fields: Option<HashMap<String, String>>,
+    /// `MD5` or `SHA256` if the storage backend wants a checksum with a `PUT` upload.
+    #[serde(default, rename = "checksumAlgorithm")]
+    pub checksum_algorithm: Option<String>,
 }
 
 /// POST /jobs/from-url response.
 #[derive(Deserialize)]
 pub(crate) struct CreateJobFromUrlResponse {
-    pub data: CreateJobFromUrlData,
-}
-
-#[derive(Deserialize)]
-pub(crate) struct CreateJobFromUrlData {
-    #[serde(rename = "jobId")]
-    pub job_id: String,
-    #[allow(dead_code)]
-    pub status: String,
+    /// The new job, kept whole as its `raw`.
+    pub data: serde_json::Value,
 }
 
 /// GET /jobs/{jobId} response.
@@ -298,12 +942,20 @@ pub(crate) struct GetJobResponse {
     pub data: serde_json::Value,
 }
 
+/// GET /jobs/{jobId} response, reading only the status.
+#[derive(Deserialize)]
+pub(crate) struct GetJobStatusResponse {
+    pub data: JobStatus,
+}
+
 /// GET /jobs response.
 #[derive(Deserialize)]
 pub(crate) struct ListJobsResponse {
     pub data: Vec<serde_json::Value>,
     #[serde(rename = "nextCursor")]
     pub next_cursor: Option<String>,
+    #[serde(default)]
+    pub total: Option<u64>,
 }
 
 /// GET /quota response.
@@ -345,6 +997,15 @@ pub(crate) struct GetAudioTrackResponse {
     pub data: AudioTrackTranscript,
 }
 
+/// URL of an artifact from a job payload: `artifacts.<kind>`, at the top level or
+/// under `processedData`.
+pub(crate) fn artifact_url(val: &serde_json::Value, kind: ArtifactKind) -> Option<&str> {
+    std::iter::once(val)
+        .chain(val.get("processedData"))
+        .find_map(|v| v.get("artifacts")?.get(kind.key())?.as_str())
+        .filter(|url| !url.is_empty())
+}
+
 /// Pull a string out of a JSON value, or `""` if missing.
 pub(crate) fn json_str(val: &serde_json::Value, key: &str) -> String {
     val.get(key)
@@ -353,11 +1014,48 @@ pub(crate) fn json_str(val: &serde_json::Value, key: &str) -> String {
         .to_string()
 }
 
+/// Pull a bool out of a JSON value, or `false` if missing.
+pub(crate) fn json_bool(val: &serde_json::Value, key: &str) -> bool {
+    val.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Pull a string out of a JSON value, or `None`.
+pub(crate) fn json_str_opt(val: &serde_json::Value, key: &str) -> Option<String> {
+    val.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Pull the strings out of a JSON array, or an empty `Vec` if missing.
+fn json_str_vec(val: &serde_json::Value, key: &str) -> Vec<String> {
+    val.get(key)
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "chrono")]
+fn parse_timestamp(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|t| t.with_timezone(&chrono::Utc))
+}
+
 /// Pull an `f64` out of a JSON value, or `None`.
 pub(crate) fn json_f64_opt(val: &serde_json::Value, key: &str) -> Option<f64> {
     val.get(key).and_then(|v| v.as_f64())
 }
 
+/// `billedHours` from the job, or from its `processedData`.
+fn billed_hours(val: &serde_json::Value) -> Option<f64> {
+    json_f64_opt(val, "billedHours").or_else(|| {
+        val.get("processedData")
+            .and_then(|p| json_f64_opt(p, "billedHours"))
+    })
+}
+
 /// Parse a raw job JSON value into a [`Job`].
 pub(crate) fn job_from_value(val: serde_json::Value) -> Job {
     let audio_track_count = val
@@ -368,15 +1066,7 @@ pub(crate) fn job_from_value(val: serde_json::Value) -> Job {
         .get("audioTracksCompleted")
         .and_then(|v| v.as_u64())
         .map(|n| n as u32);
-    let audio_track_names: Vec<String> = val
-        .get("audioTrackNames")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        })
-        .unwrap_or_default();
+    let audio_track_names = json_str_vec(&val, "audioTrackNames");
 
     Job {
         id: json_str(&val, "jobId"),
@@ -387,28 +1077,92 @@ pub(crate) fn job_from_value(val: serde_json::Value) -> Job {
         audio_track_count,
         audio_tracks_completed,
         audio_track_names,
+        from_cache: json_bool(&val, "fromCache"),
+        failure_reason: json_str_opt(&val, "failureReason"),
+        error_code: json_str_opt(&val, "errorCode"),
+        tags: json_str_vec(&val, "tags"),
+        billed_hours: billed_hours(&val),
+        progress: json_f64_opt(&val, "progress"),
+        upload_checksum: None,
         raw: val,
     }
 }
 
+/// Deserialize `processedData.transcript` in place, without cloning it, sorted by
+/// start time (stable, so ties keep their API order).
+pub(crate) fn transcript_from_value(val: &serde_json::Value) -> Vec<TranscriptSegment> {
+    let mut transcript = val
+        .get("processedData")
+        .and_then(|p| p.get("transcript"))
+        .and_then(|v| Vec::<TranscriptSegment>::deserialize(v).ok())
+        .unwrap_or_default();
+    transcript.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    transcript
+}
+
+/// The first of `processedData.scenes` / `.transcript` that's present but fails to
+/// deserialize, with the error and the offending value. The lenient parsers read such a
+/// field as empty.
+pub(crate) fn processed_data_mismatch(
+    val: &serde_json::Value,
+) -> Option<(&'static str, serde_json::Error, &serde_json::Value)> {
+    let processed = val.get("processedData")?;
+    let field = |key: &str| processed.get(key).filter(|v| !v.is_null());
+    if let Some(scenes) = field("scenes") {
+        if let Err(e) = Vec::<Scene>::deserialize(scenes) {
+            return Some(("scenes", e, scenes));
+        }
+    }
+    if let Some(transcript) = field("transcript") {
+        if let Err(e) = Vec::<TranscriptSegment>::deserialize(transcript) {
+            return Some(("transcript", e, transcript));
+        }
+    }
+    None
+}
+
+/// Read [`VideoMetadata`] from `processedData`, falling back to the top level of the job.
+fn video_metadata_from_value(val: &serde_json::Value) -> VideoMetadata {
+    let field = |key: &str| {
+        val.get("processedData")
+            .and_then(|p| p.get(key))
+            .or_else(|| val.get(key))
+            .filter(|v| !v.is_null())
+    };
+    VideoMetadata {
+        width: field("width")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| u32::try_from(n).ok()),
+        height: field("height")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| u32::try_from(n).ok()),
+        fps: field("fps").and_then(|v| v.as_f64()),
+        codec: field("codec").and_then(|v| v.as_str()).map(String::from),
+        size_bytes: field("sizeBytes").and_then(|v| v.as_u64()),
+    }
+}
+
 /// Parse a completed job's JSON into a [`ProcessingResult`].
+///
+/// Scenes and transcript are deserialized straight from borrowed sub-values
+/// (`&Value` implements `Deserializer`) rather than cloned out first, so a large
+/// payload is held once, in `raw`, plus its typed copy.
 pub(crate) fn processing_result_from_value(val: serde_json::Value) -> ProcessingResult {
-    let processed = val.get("processedData").cloned().unwrap_or_default();
+    let processed = val.get("processedData");
 
     let duration = processed
-        .get("length")
+        .and_then(|p| p.get("length"))
         .and_then(|v| v.as_f64())
         .unwrap_or(0.0);
 
-    let scenes: Vec<Scene> = processed
-        .get("scenes")
-        .and_then(|v| serde_json::from_value(v.clone()).ok())
+    let mut scenes: Vec<Scene> = processed
+        .and_then(|p| p.get("scenes"))
+        .and_then(|v| Vec::<Scene>::deserialize(v).ok())
         .unwrap_or_default();
+    // The API doesn't promise an order. Stable, so ties keep their API order.
+    scenes.sort_by(|a, b| a.end_time.total_cmp(&b.end_time));
 
-    let transcript: Vec<TranscriptSegment> = processed
-        .get("transcript")
-        .and_then(|v| serde_json::from_value(v.clone()).ok())
-        .unwrap_or_default();
+    let transcript = transcript_from_value(&val);
 
     ProcessingResult {
         job_id: json_str(&val, "jobId"),
@@ -417,7 +1171,225 @@ pub(crate) fn processing_result_from_value(val: serde_json::Value) -> Processing
         duration,
         scenes,
         transcript,
+        metadata: video_metadata_from_value(&val),
+        from_cache: json_bool(&val, "fromCache"),
         created_at: json_str(&val, "createdAt"),
+        detected_language: processed
+            .and_then(|p| json_str_opt(p, "detectedLanguage"))
+            .or_else(|| json_str_opt(&val, "detectedLanguage")),
+        billed_hours: billed_hours(&val),
+        scenes_attempted: processed
+            .and_then(|p| p.get("scenes"))
+            .is_some_and(|s| !s.is_null()),
         raw: val,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn created_at_utc_parses_or_returns_none() {
+        use chrono::TimeZone;
+
+        let job = |created_at: &str| job_from_value(json!({ "createdAt": created_at }));
+        assert_eq!(
+            job("2025-01-15T10:30:00+02:00").created_at_utc(),
+            Some(chrono::Utc.with_ymd_and_hms(2025, 1, 15, 8, 30, 0).unwrap())
+        );
+        assert_eq!(job("").created_at_utc(), None);
+        assert_eq!(job("last tuesday").created_at_utc(), None);
+    }
+
+    #[test]
+    fn changed_since_reports_status_and_eta() {
+        let before = job_from_value(
+            json!({ "status": "PROCESSING", "estimatedCompletionTimeSeconds": 30.0 }),
+        );
+        let after = job_from_value(json!({ "status": "VISION_COMPLETED" }));
+
+        let delta = after.changed_since(&before);
+        assert_eq!(
+            delta.status,
+            Some(("PROCESSING".into(), "VISION_COMPLETED".into()))
+        );
+        assert_eq!(delta.eta_seconds, Some((Some(30.0), None)));
+        assert_eq!(
+            delta.to_string(),
+            "status PROCESSING → VISION_COMPLETED, eta 30s → none"
+        );
+        assert!(after.changed_since(&after).is_empty());
+    }
+
+    #[test]
+    fn detected_language_is_read_from_processed_data() {
+        let result = processing_result_from_value(json!({
+            "processedData": {
+                "detectedLanguage": "de",
+                "transcript": [
+                    { "StartTime": 0.0, "EndTime": 1.0, "Text": "Hallo", "Language": "de" },
+                    { "StartTime": 1.0, "EndTime": 2.0, "Text": "hello" },
+                ],
+            },
+        }));
+        assert_eq!(result.detected_language.as_deref(), Some("de"));
+        assert_eq!(result.transcript[0].language.as_deref(), Some("de"));
+        assert_eq!(result.transcript[1].language, None);
+
+        let result = processing_result_from_value(json!({ "processedData": {} }));
+        assert_eq!(result.detected_language, None);
+    }
+
+    #[test]
+    fn billed_hours_is_read_from_job_or_processed_data() {
+        let val = json!({ "status": "COMPLETED", "billedHours": 0.25, "processedData": {} });
+        assert_eq!(job_from_value(val.clone()).billed_hours, Some(0.25));
+        assert_eq!(processing_result_from_value(val).billed_hours, Some(0.25));
+
+        let nested = json!({ "processedData": { "billedHours": 1.5 } });
+        assert_eq!(processing_result_from_value(nested).billed_hours, Some(1.5));
+        assert_eq!(
+            job_from_value(json!({ "status": "PROCESSING" })).billed_hours,
+            None
+        );
+    }
+
+    #[test]
+    fn plan_enum_keeps_unknown_plans() {
+        let quota = |plan: &str| Quota {
+            plan: plan.into(),
+            included_hours: 0.0,
+            credits_balance_hours: 0.0,
+            reset_date: None,
+        };
+        assert_eq!(quota("pro").plan_enum(), Plan::Pro);
+        assert_eq!(quota("Enterprise").plan_enum(), Plan::Enterprise);
+        assert_eq!(quota("team").plan_enum(), Plan::Other("team".into()));
+    }
+
+    fn result_with_transcript(segments: &[(f64, f64, &str)]) -> ProcessingResult {
+        let transcript: Vec<_> = segments
+            .iter()
+            .map(|(start, end, text)| json!({ "StartTime": start, "EndTime": end, "Text": text }))
+            .collect();
+        processing_result_from_value(json!({
+            "jobId": "job_1",
+            "status": "VISION_COMPLETED",
+            "processedData": { "length": 60.0, "transcript": transcript },
+        }))
+    }
+
+    fn result_with_scene_ends(ends: &[f64]) -> ProcessingResult {
+        let scenes: Vec<_> = ends
+            .iter()
+            .enumerate()
+            .map(|(i, end)| json!({ "description": format!("s{i}"), "endTs": end }))
+            .collect();
+        processing_result_from_value(json!({
+            "processedData": { "length": 60.0, "scenes": scenes },
+        }))
+    }
+
+    fn descriptions(scenes: Vec<&Scene>) -> Vec<&str> {
+        scenes.into_iter().map(|s| s.description.as_str()).collect()
+    }
+
+    #[test]
+    fn sample_scenes_spreads_over_duration_without_repeats() {
+        // Four short scenes up front, then one long one.
+        let result = result_with_scene_ends(&[2.0, 4.0, 6.0, 8.0, 60.0]);
+
+        // Slice midpoints 10s, 30s, 50s all fall in s4; earlier neighbours fill in.
+        assert_eq!(descriptions(result.sample_scenes(3)), ["s2", "s3", "s4"]);
+        assert_eq!(descriptions(result.sample_scenes(1)), ["s4"]);
+        assert_eq!(result.sample_scenes(9).len(), 5);
+        assert!(result.sample_scenes(0).is_empty());
+
+        let even = result_with_scene_ends(&[10.0, 20.0, 30.0, 40.0, 50.0, 60.0]);
+        assert_eq!(descriptions(even.sample_scenes(3)), ["s0", "s2", "s4"]);
+    }
+
+    #[test]
+    fn scenes_every_picks_the_scene_at_each_mark() {
+        let result = result_with_scene_ends(&[15.0, 25.0, 60.0]);
+
+        assert_eq!(descriptions(result.scenes_every(20.0)), ["s0", "s1", "s2"]);
+        assert_eq!(
+            descriptions(result.scenes_every(10.0)),
+            ["s0", "s0", "s1", "s2", "s2", "s2"]
+        );
+        assert!(result.scenes_every(0.0).is_empty());
+        assert!(result_with_scene_ends(&[]).scenes_every(10.0).is_empty());
+    }
+
+    #[test]
+    fn scenes_every_caps_the_marks_for_a_tiny_interval() {
+        let result = result_with_scene_ends(&[15.0, 25.0, 3600.0]);
+
+        let marks = result.scenes_every(1e-9);
+
+        assert_eq!(marks.len(), MAX_SCENE_MARKS);
+        assert_eq!(marks[0].description, "s0");
+        assert_eq!(marks[MAX_SCENE_MARKS - 1].description, "s2");
+    }
+
+    #[test]
+    fn merged_transcript_joins_only_small_gaps() {
+        let result = result_with_transcript(&[
+            (0.0, 1.0, "Hello"),
+            (1.2, 2.0, " there."),
+            (5.0, 6.0, "Later"),
+        ]);
+
+        let merged = result.merged_transcript(0.5);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].text, "Hello there.");
+        assert_eq!((merged[0].start_time, merged[0].end_time), (0.0, 2.0));
+        assert_eq!(merged[1].text, "Later");
+    }
+
+    #[test]
+    fn scenes_and_transcript_are_sorted_stably() {
+        let result = processing_result_from_value(json!({
+            "status": "VISION_COMPLETED",
+            "processedData": {
+                "scenes": [
+                    { "description": "b", "endTs": 20.0, "objects": [] },
+                    { "description": "a", "endTs": 10.0, "objects": [] },
+                    { "description": "c", "endTs": 20.0, "objects": [] },
+                ],
+                "transcript": [
+                    { "StartTime": 5.0, "EndTime": 6.0, "Text": "second" },
+                    { "StartTime": 1.0, "EndTime": 2.0, "Text": "first" },
+                ],
+            },
+        }));
+
+        let scenes: Vec<_> = result
+            .scenes
+            .iter()
+            .map(|s| s.description.as_str())
+            .collect();
+        assert_eq!(scenes, ["a", "b", "c"]);
+        assert_eq!(result.transcript[0].text, "first");
+    }
+
+    #[test]
+    fn word_index_normalizes_case_and_punctuation() {
+        let result = result_with_transcript(&[
+            (0.0, 1.0, "Hello, world! Hello."),
+            (4.5, 6.0, "Don't say \"hello\""),
+        ]);
+
+        let index = result.build_word_index();
+
+        assert_eq!(index["hello"], [0.0, 4.5]);
+        assert_eq!(index["world"], [0.0]);
+        assert_eq!(index["don't"], [4.5]);
+        assert!(!index.contains_key("hello,"));
+    }
+}
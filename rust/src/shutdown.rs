@@ -0,0 +1,111 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+
+use futures::future::{select, Either};
+use tokio::sync::Notify;
+
+use crate::errors::{FrameQueryError, Result};
+
+/// Shutdown signal and the jobs being polled, shared by a client and its clones. See
+/// `Client::shutdown`.
+#[derive(Default)]
+pub(crate) struct Shutdown {
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+#[derive(Default)]
+struct State {
+    triggered: bool,
+    /// One entry per running poll loop; a job polled twice appears twice.
+    polling: Vec<String>,
+}
+
+/// Keeps job ids listed as being polled until dropped. From [`Shutdown::register`].
+pub(crate) struct Registration {
+    shutdown: Arc<Shutdown>,
+    job_ids: Vec<String>,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut state = self.shutdown.state.lock().unwrap();
+        for id in &self.job_ids {
+            if let Some(i) = state.polling.iter().position(|p| p == id) {
+                state.polling.swap_remove(i);
+            }
+        }
+    }
+}
+
+impl Shutdown {
+    /// Stop every tracked poll loop, and any started later. Returns the ids still being
+    /// polled, sorted and deduplicated.
+    pub(crate) fn trigger(&self) -> Vec<String> {
+        // Snapshot before waking anyone, or the woken loops could deregister first.
+        let mut ids = {
+            let mut state = self.state.lock().unwrap();
+            state.triggered = true;
+            state.polling.clone()
+        };
+        self.notify.notify_waiters();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// List `job_ids` as being polled, or `None` if shutdown was already triggered.
+    /// Registering and checking under one lock means a concurrent `trigger` either
+    /// reports these ids or is seen here.
+    pub(crate) fn register(self: &Arc<Self>, job_ids: &[&str]) -> Option<Registration> {
+        let mut state = self.state.lock().unwrap();
+        if state.triggered {
+            return None;
+        }
+        state
+            .polling
+            .extend(job_ids.iter().map(|id| id.to_string()));
+        Some(Registration {
+            shutdown: self.clone(),
+            job_ids: job_ids.iter().map(|id| id.to_string()).collect(),
+        })
+    }
+
+    /// Run `fut`, unless shutdown is or gets triggered first, which returns `Cancelled`
+    /// for `job_id`. Doesn't register anything; see [`register`](Self::register).
+    pub(crate) async fn until_triggered<T>(
+        &self,
+        job_id: &str,
+        fut: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let cancelled = || FrameQueryError::Cancelled {
+            job_id: job_id.to_string(),
+        };
+        // Created before the flag check, so a trigger in between still wakes us.
+        let notified = self.notify.notified();
+        if self.state.lock().unwrap().triggered {
+            return Err(cancelled());
+        }
+        match select(pin!(notified), pin!(fut)).await {
+            Either::Left(_) => Err(cancelled()),
+            Either::Right((result, _)) => result,
+        }
+    }
+
+    /// Run `fut`, which polls `job_ids`, with them registered, unless shutdown
+    /// interrupts it with `Cancelled` for the first of them.
+    pub(crate) async fn track<T>(
+        self: &Arc<Self>,
+        job_ids: &[&str],
+        fut: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let first = job_ids.first().copied().unwrap_or_default();
+        let Some(_registration) = self.register(job_ids) else {
+            return Err(FrameQueryError::Cancelled {
+                job_id: first.to_string(),
+            });
+        };
+        self.until_triggered(first, fut).await
+    }
+}
@@ -0,0 +1,48 @@
+use crate::errors::Result;
+
+/// Replaces the HTTP layer for JSON API calls, so code built on [`Client`](crate::Client)
+/// can be tested without a network. See [`Client::with_transport`](crate::Client::with_transport).
+///
+/// `path` is relative to the base URL and includes any query string, e.g. `"/jobs/abc"`
+/// or `"/jobs?limit=10"`. Return the full response envelope (e.g. `{"data": {...}}`), or
+/// an error to simulate a failure. Retries don't apply.
+///
+/// Closures of the right shape implement this trait:
+///
+/// ```
+/// use framequery::{Client, FrameQueryError};
+/// use serde_json::json;
+///
+/// let client = Client::with_transport(
+///     |method: &str, path: &str, _body: Option<&serde_json::Value>| match (method, path) {
+///         ("GET", "/jobs/abc") => Ok(json!({
+///             "data": { "jobId": "abc", "status": "PROCESSING" }
+///         })),
+///         _ => Err(FrameQueryError::NotFound {
+///             message: path.to_string(),
+///         }),
+///     },
+/// );
+/// ```
+pub trait Transport: Send + Sync {
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value>;
+}
+
+impl<F> Transport for F
+where
+    F: Fn(&str, &str, Option<&serde_json::Value>) -> Result<serde_json::Value> + Send + Sync,
+{
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        self(method, path, body)
+    }
+}
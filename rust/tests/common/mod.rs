@@ -0,0 +1,25 @@
+//! Fixtures shared by the integration tests that upload files.
+
+use std::time::Duration;
+
+use framequery::ClientBuilder;
+use wiremock::MockServer;
+
+/// A client for `server`, retrying twice without backoff.
+pub fn client(server: &MockServer) -> framequery::Client {
+    ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .max_retries(2)
+        .max_backoff(Duration::ZERO)
+        .build()
+        .unwrap()
+}
+
+/// A temp `.mp4` named after `name`: an MP4 signature, so `upload`'s media check passes,
+/// followed by junk.
+pub fn video_file(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("fq-{}-{name}.mp4", std::process::id()));
+    std::fs::write(&path, b"\0\0\0\x18ftypisomnot really a video").unwrap();
+    path
+}
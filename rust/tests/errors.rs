@@ -0,0 +1,204 @@
+use framequery::{ClientBuilder, FrameQueryError};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn quota_error(status: u16, body: serde_json::Value) -> FrameQueryError {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(status).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .get_quota()
+        .await
+        .unwrap_err()
+}
+
+#[tokio::test]
+async fn payment_required_maps_to_quota_exceeded() {
+    let err = quota_error(
+        402,
+        json!({ "error": "out of hours", "neededHours": 2.5, "availableHours": 0.5 }),
+    )
+    .await;
+
+    assert!(matches!(
+        err,
+        FrameQueryError::QuotaExceeded {
+            needed_hours: Some(n),
+            available_hours: Some(a),
+            ..
+        } if n == 2.5 && a == 0.5
+    ));
+}
+
+#[tokio::test]
+async fn forbidden_with_quota_code_maps_to_quota_exceeded() {
+    let err = quota_error(
+        403,
+        json!({ "error": "out of hours", "code": "QUOTA_EXCEEDED" }),
+    )
+    .await;
+    assert!(matches!(err, FrameQueryError::QuotaExceeded { .. }));
+
+    let err = quota_error(403, json!({ "error": "no access" })).await;
+    assert!(matches!(err, FrameQueryError::PermissionDenied { .. }));
+}
+
+#[tokio::test]
+async fn unparseable_success_body_is_a_decode_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>login</html>"))
+        .mount(&server)
+        .await;
+
+    let err = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .get_quota()
+        .await
+        .unwrap_err();
+
+    assert!(err.is_decode());
+    match err {
+        FrameQueryError::Decode { body_snippet, .. } => {
+            assert_eq!(body_snippet, "<html>login</html>")
+        }
+        other => panic!("expected Decode, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn unsolicited_not_modified_is_an_api_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(304))
+        .mount(&server)
+        .await;
+
+    let err = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .get_quota()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            err,
+            FrameQueryError::Api {
+                status_code: 304,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn ping_reports_a_bad_key_as_authentication() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({ "error": "invalid key" })))
+        .mount(&server)
+        .await;
+
+    let err = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .ping()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FrameQueryError::Authentication { .. }));
+}
+
+#[tokio::test]
+async fn raw_request_never_falls_back_to_get() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/job_1/notes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": true })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    let body = client
+        .raw_request("post", "/jobs/job_1/notes", None)
+        .await
+        .unwrap();
+    assert_eq!(body, json!({ "ok": true }));
+
+    let err = client
+        .raw_request("TRACE", "/jobs/job_1/notes", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FrameQueryError::Config { .. }), "{err:?}");
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[test]
+fn errors_and_client_are_send_sync() {
+    // Required to use them behind `anyhow` / `tower` or across `tokio::spawn`; this
+    // fails to compile if a field ever stops being thread-safe.
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<FrameQueryError>();
+    assert_send_sync::<framequery::Client>();
+    assert_send_sync::<framequery::JobHandle>();
+}
+
+#[tokio::test]
+async fn ping_accepts_only_a_200() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    let err = client.ping().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            FrameQueryError::Api {
+                status_code: 204,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {} })))
+        .mount(&server)
+        .await;
+    client.ping().await.unwrap();
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{client, video_file};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn server(expected_cancels: u64) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "jobId": "job_1",
+                "uploadUrl": format!("{}/upload/1", server.uri()),
+                "expiresInSeconds": 3600,
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/job_1/cancel"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {} })))
+        .expect(expected_cancels)
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Give the detached cancel task time to run.
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(200)).await;
+}
+
+#[tokio::test]
+async fn dropping_handle_cancels_job() {
+    let server = server(1).await;
+    let file = video_file("drop");
+
+    let handle = client(&server).start(&file, None).await.unwrap();
+    assert_eq!(handle.id(), "job_1");
+    drop(handle);
+    settle().await;
+
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[tokio::test]
+async fn forgotten_handle_leaves_job_running() {
+    let server = server(0).await;
+    let file = video_file("forget");
+
+    let handle = client(&server).start(&file, None).await.unwrap();
+    assert_eq!(handle.forget(), "job_1");
+    settle().await;
+
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[tokio::test]
+async fn handle_stopped_by_shutdown_leaves_job_running() {
+    let server = server(0).await;
+    let file = video_file("shutdown");
+
+    let client = client(&server);
+    let mut handle = client.start(&file, None).await.unwrap();
+    client.shutdown();
+    let err = handle.wait(&Default::default()).await.unwrap_err();
+    assert!(matches!(err, framequery::FrameQueryError::Cancelled { .. }));
+    drop(handle);
+    settle().await;
+
+    std::fs::remove_file(&file).unwrap();
+}
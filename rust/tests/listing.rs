@@ -0,0 +1,171 @@
+use framequery::{ClientBuilder, FrameQueryError, ListJobsOptions};
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn fields_are_sent_as_one_comma_separated_param() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs"))
+        .and(query_param("fields", "jobId,status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{ "jobId": "job_1", "status": "PROCESSING" }],
+            "nextCursor": null,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let page = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .list_jobs_with(&ListJobsOptions {
+            fields: Some(vec!["jobId".into(), "status".into()]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(page.jobs[0].id, "job_1");
+    assert_eq!(page.jobs[0].filename, "");
+}
+
+#[tokio::test]
+async fn tags_filter_is_url_encoded_and_parsed_back() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs"))
+        .and(query_param("tags", "acme corp,r&d"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{ "jobId": "job_1", "status": "PROCESSING", "tags": ["acme corp", "r&d"] }],
+            "nextCursor": null,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let page = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .list_jobs_with(&ListJobsOptions {
+            tags: Some(vec!["acme corp".into(), "r&d".into()]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(page.jobs[0].tags, ["acme corp", "r&d"]);
+}
+
+#[tokio::test]
+async fn only_cursor_related_400s_are_invalid_cursor() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs"))
+        .and(query_param("cursor", "stale"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "cursor has expired",
+            "code": "INVALID_CURSOR",
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs"))
+        .and(query_param("cursor", "fresh"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "createdAfter must be ISO 8601",
+            "code": "INVALID_PARAMETER",
+        })))
+        .mount(&server)
+        .await;
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let list = |cursor: &str| {
+        let opts = ListJobsOptions {
+            cursor: Some(cursor.to_string()),
+            created_after: Some("yesterday".into()),
+            ..Default::default()
+        };
+        let client = &client;
+        async move { client.list_jobs_with(&opts).await }
+    };
+
+    let err = list("stale").await.unwrap_err();
+    assert!(
+        matches!(err, FrameQueryError::InvalidCursor { ref cursor, status_code: 400, .. } if cursor == "stale"),
+        "{err:?}"
+    );
+    let err = list("fresh").await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            FrameQueryError::Api {
+                status_code: 400,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn cancel_jobs_where_sweeps_every_page_and_records_failures() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs"))
+        .and(query_param("status", "PENDING_UPLOAD"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{ "jobId": "job_3" }],
+            "nextCursor": null,
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs"))
+        .and(query_param("status", "PENDING_UPLOAD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{ "jobId": "job_1" }, { "jobId": "job_2" }],
+            "nextCursor": "page2",
+        })))
+        .mount(&server)
+        .await;
+    for (id, status) in [("job_1", 200), ("job_2", 409), ("job_3", 200)] {
+        Mock::given(method("POST"))
+            .and(path(format!("/v1/api/jobs/{id}/cancel")))
+            .respond_with(
+                ResponseTemplate::new(status).set_body_json(json!({ "error": "already done" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let summary = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+        .cancel_jobs_where("PENDING_UPLOAD")
+        .await
+        .unwrap();
+
+    assert_eq!(summary.cancelled, ["job_1", "job_3"]);
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(summary.failed[0].0, "job_2");
+    assert!(matches!(
+        summary.failed[0].1,
+        FrameQueryError::Api {
+            status_code: 409,
+            ..
+        }
+    ));
+}
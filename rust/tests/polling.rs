@@ -0,0 +1,591 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use framequery::{
+    BatchClip, BatchOptions, Client, ClientBuilder, FrameQueryError, Metrics, ProcessOptions,
+    ProgressUpdate, SceneStatus,
+};
+use serde_json::json;
+use tokio::time::Instant;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// A client whose job stays `PROCESSING` for `polls_until_done` polls, then completes.
+/// `u32::MAX` never completes.
+fn client(polls_until_done: u32) -> (Client, Arc<AtomicU32>) {
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    let client = Client::with_transport(move |_: &str, _: &str, _: Option<&serde_json::Value>| {
+        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+        let status = if n >= polls_until_done {
+            "VISION_COMPLETED"
+        } else {
+            "PROCESSING"
+        };
+        Ok(json!({ "data": { "jobId": "job_1", "status": status } }))
+    });
+    (client, calls)
+}
+
+fn opts(poll_interval: u64, timeout: u64) -> ProcessOptions {
+    ProcessOptions {
+        poll_interval: Duration::from_secs(poll_interval),
+        timeout: Duration::from_secs(timeout),
+        ..Default::default()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn timeout_fires_at_exactly_opts_timeout() {
+    let (client, calls) = client(u32::MAX);
+    let started = Instant::now();
+
+    let err = client
+        .wait_for_job("job_1", &opts(5, 12))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, FrameQueryError::Timeout(t) if t == Duration::from_secs(12)));
+    assert_eq!(started.elapsed(), Duration::from_secs(12));
+    // Polls at 0s, 5s, 10s, and a final one at the 12s deadline.
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn wait_for_job_timeout_uses_default_options_with_the_given_timeout() {
+    let (client, calls) = client(u32::MAX);
+
+    let err = client
+        .wait_for_job_timeout("job_1", Duration::from_secs(12))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, FrameQueryError::Timeout(t) if t == Duration::from_secs(12)));
+    // Default 5s interval: 0s, 5s, 10s, 12s.
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn timeout_on_interval_boundary_does_not_overshoot() {
+    let (client, _) = client(u32::MAX);
+    let started = Instant::now();
+
+    let err = client
+        .wait_for_job("job_1", &opts(5, 10))
+        .await
+        .unwrap_err();
+
+    assert!(err.is_timeout());
+    assert_eq!(started.elapsed(), Duration::from_secs(10));
+}
+
+#[tokio::test(start_paused = true)]
+async fn completion_before_deadline_returns_result() {
+    let (client, calls) = client(3);
+    let started = Instant::now();
+
+    let result = client.wait_for_job("job_1", &opts(5, 60)).await.unwrap();
+
+    assert_eq!(result.job_id, "job_1");
+    assert_eq!(started.elapsed(), Duration::from_secs(10));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn on_update_smooths_eta_into_estimated_completion() {
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    let client = Client::with_transport(move |_: &str, _: &str, _: Option<&serde_json::Value>| {
+        let job = match counter.fetch_add(1, Ordering::SeqCst) {
+            0 => {
+                json!({ "jobId": "job_1", "status": "PROCESSING", "estimatedCompletionTimeSeconds": 100 })
+            }
+            1 => {
+                json!({ "jobId": "job_1", "status": "PROCESSING", "estimatedCompletionTimeSeconds": 50 })
+            }
+            _ => json!({ "jobId": "job_1", "status": "VISION_COMPLETED" }),
+        };
+        Ok(json!({ "data": job }))
+    });
+    let estimates = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = estimates.clone();
+    let started = Instant::now().into_std();
+    let opts = ProcessOptions {
+        on_update: Some(Arc::new(move |update: &ProgressUpdate<'_>| {
+            seen.lock().unwrap().push(update.estimated_completion);
+        })),
+        ..opts(5, 60)
+    };
+
+    client.wait_for_job("job_1", &opts).await.unwrap();
+
+    // 100s at t=0, then 5s + 50s = 55s weighted 0.3 against it; the final poll sends
+    // no ETA and keeps the estimate.
+    let expected = started + Duration::from_secs_f64(86.5);
+    let estimates = estimates.lock().unwrap();
+    assert_eq!(estimates.len(), 3);
+    assert_eq!(estimates[0], Some(started + Duration::from_secs(100)));
+    for estimate in &estimates[1..] {
+        let diff = estimate.unwrap().saturating_duration_since(expected)
+            + expected.saturating_duration_since(estimate.unwrap());
+        assert!(diff < Duration::from_millis(1), "{estimate:?}");
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn light_polling_fetches_full_job_once_on_completion() {
+    let paths = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = paths.clone();
+    let client =
+        Client::with_transport(move |_: &str, path: &str, _: Option<&serde_json::Value>| {
+            let mut seen = seen.lock().unwrap();
+            seen.push(path.to_string());
+            let status = if seen.len() >= 3 {
+                "VISION_COMPLETED"
+            } else {
+                "PROCESSING"
+            };
+            Ok(json!({ "data": { "jobId": "job_1", "status": status } }))
+        });
+    let opts = ProcessOptions {
+        light_polling: true,
+        ..opts(5, 60)
+    };
+
+    client.wait_for_job("job_1", &opts).await.unwrap();
+
+    assert_eq!(
+        *paths.lock().unwrap(),
+        [
+            "/jobs/job_1?light=true",
+            "/jobs/job_1?light=true",
+            "/jobs/job_1?light=true",
+            "/jobs/job_1",
+        ]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_deadline_cuts_polling_short() {
+    let (client, _) = client(u32::MAX);
+    let started = Instant::now();
+    let client = client.with_deadline(started + Duration::from_secs(7));
+
+    let err = client
+        .wait_for_job("job_1", &opts(5, 60))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, FrameQueryError::Timeout(t) if t == Duration::from_secs(7)));
+    assert_eq!(started.elapsed(), Duration::from_secs(7));
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_job_reports_reason_and_code() {
+    let client = Client::with_transport(|_: &str, _: &str, _: Option<&serde_json::Value>| {
+        Ok(json!({ "data": {
+            "jobId": "job_1",
+            "status": "INGEST_FAILED_TRANSCODE",
+            "failureReason": "unsupported codec: prores",
+            "errorCode": "UNSUPPORTED_CODEC",
+        } }))
+    });
+
+    let err = client
+        .wait_for_job("job_1", &opts(5, 60))
+        .await
+        .unwrap_err();
+
+    match err {
+        FrameQueryError::JobFailed {
+            ref job_id,
+            ref reason,
+            ref error_code,
+            ..
+        } => {
+            assert_eq!(job_id, "job_1");
+            assert_eq!(reason.as_deref(), Some("unsupported codec: prores"));
+            assert_eq!(error_code.as_deref(), Some("UNSUPPORTED_CODEC"));
+        }
+        other => panic!("expected JobFailed, got {other:?}"),
+    }
+    assert_eq!(
+        err.to_string(),
+        "job job_1 failed (INGEST_FAILED_TRANSCODE): unsupported codec: prores [UNSUPPORTED_CODEC]"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn initial_delay_postpones_first_poll() {
+    let (client, calls) = client(1);
+    let started = Instant::now();
+    let opts = ProcessOptions {
+        initial_delay: Duration::from_secs(3),
+        ..opts(5, 60)
+    };
+
+    client.wait_for_job("job_1", &opts).await.unwrap();
+
+    assert_eq!(started.elapsed(), Duration::from_secs(3));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn transient_not_found_right_after_creation_is_tolerated() {
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    let client =
+        Client::with_transport(move |_: &str, path: &str, _: Option<&serde_json::Value>| {
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                return Err(FrameQueryError::NotFound {
+                    message: path.to_string(),
+                });
+            }
+            Ok(json!({ "data": { "jobId": "job_1", "status": "VISION_COMPLETED" } }))
+        });
+
+    client.wait_for_job("job_1", &opts(5, 60)).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn not_found_retries_count_toward_max_polls() {
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    let client =
+        Client::with_transport(move |_: &str, path: &str, _: Option<&serde_json::Value>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Err(FrameQueryError::NotFound {
+                message: path.to_string(),
+            })
+        });
+    let opts = ProcessOptions {
+        max_polls: Some(2),
+        ..opts(5, 60)
+    };
+
+    let err = client.wait_for_job("job_1", &opts).await.unwrap_err();
+    assert!(matches!(err, FrameQueryError::NotFound { .. }), "{err:?}");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn unchanged_polls_reuse_the_etag_cached_job() {
+    let server = MockServer::start().await;
+    let processing = json!({ "data": { "jobId": "job_1", "status": "PROCESSING" } });
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v1\"")
+                .set_body_json(&processing),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "data": { "jobId": "job_1", "status": "VISION_COMPLETED" } }),
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let statuses = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = statuses.clone();
+    let opts = ProcessOptions {
+        poll_interval: Duration::from_millis(1),
+        on_progress: Some(Arc::new(move |job: &framequery::Job| {
+            seen.lock().unwrap().push(job.status.clone())
+        })),
+        ..Default::default()
+    };
+
+    client.wait_for_job("job_1", &opts).await.unwrap();
+
+    assert_eq!(
+        *statuses.lock().unwrap(),
+        ["PROCESSING", "PROCESSING", "PROCESSING", "VISION_COMPLETED"]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_stops_polling_and_reports_pending_jobs() {
+    let (client, _) = client(u32::MAX);
+    let task = tokio::spawn({
+        let client = client.clone();
+        async move { client.wait_for_job("job_1", &opts(5, 600)).await }
+    });
+    tokio::time::sleep(Duration::from_secs(12)).await;
+
+    assert_eq!(client.shutdown(), ["job_1"]);
+    let err = task.await.unwrap().unwrap_err();
+    assert!(matches!(err, FrameQueryError::Cancelled { ref job_id } if job_id == "job_1"));
+
+    let err = client
+        .wait_for_job("job_2", &opts(5, 600))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FrameQueryError::Cancelled { ref job_id } if job_id == "job_2"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn shutdown_reports_every_job_polled_on_other_threads() {
+    let polled = Arc::new(std::sync::Mutex::new(std::collections::HashSet::new()));
+    let seen = polled.clone();
+    let client =
+        Client::with_transport(move |_: &str, path: &str, _: Option<&serde_json::Value>| {
+            seen.lock().unwrap().insert(path.to_string());
+            Ok(json!({ "data": { "jobId": "job", "status": "PROCESSING" } }))
+        });
+    let opts = ProcessOptions {
+        poll_interval: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let ids: Vec<String> = (0..16).map(|i| format!("job_{i:02}")).collect();
+    let tasks: Vec<_> = ids
+        .iter()
+        .map(|id| {
+            let (client, opts, id) = (client.clone(), opts.clone(), id.clone());
+            tokio::spawn(async move { client.wait_for_job(&id, &opts).await })
+        })
+        .collect();
+    while polled.lock().unwrap().len() < ids.len() {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    // Woken loops deregister as they exit; none may do so before the snapshot.
+    assert_eq!(client.shutdown(), ids);
+    for task in tasks {
+        let err = task.await.unwrap().unwrap_err();
+        assert!(matches!(err, FrameQueryError::Cancelled { .. }), "{err:?}");
+    }
+}
+
+#[tokio::test]
+async fn retain_raw_false_drops_raw_but_keeps_typed_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_1",
+            "status": "VISION_COMPLETED",
+            "originalFilename": "talk.mp4",
+            "processedData": {
+                "length": 30.0,
+                "scenes": [{ "description": "stage", "endTs": 30.0, "objects": ["person"] }],
+                "transcript": [{ "StartTime": 0.0, "EndTime": 2.0, "Text": "hello" }],
+            },
+        } })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_2",
+            "status": "PROCESSING",
+            "progress": 40,
+        } })))
+        .mount(&server)
+        .await;
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .retain_raw(false)
+        .build()
+        .unwrap();
+
+    let result = client
+        .wait_for_job("job_1", &ProcessOptions::default())
+        .await
+        .unwrap();
+    assert!(result.raw.is_null());
+    assert_eq!(result.filename, "talk.mp4");
+    assert_eq!(result.duration, 30.0);
+    assert_eq!(result.scenes[0].objects, ["person"]);
+    assert_eq!(result.transcript[0].text, "hello");
+    assert_eq!(result.scene_status(), SceneStatus::Detected);
+
+    let job = client.get_job("job_1").await.unwrap();
+    assert!(job.raw.is_null());
+    assert!(job.is_complete());
+
+    let job = client.get_job("job_2").await.unwrap();
+    assert!(job.raw.is_null());
+    assert_eq!(job.progress_fraction(), Some(0.4));
+}
+
+#[derive(Clone, Default)]
+struct FinishedJobs(Arc<Mutex<Vec<String>>>);
+
+impl Metrics for FinishedJobs {
+    fn on_job_finished(&self, status: &str, _elapsed: Duration) {
+        self.0.lock().unwrap().push(status.to_string());
+    }
+}
+
+#[tokio::test]
+async fn process_batch_reports_each_finished_job_to_metrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/batch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "batchId": "b1",
+            "mode": "independent",
+            "jobs": [
+                { "jobId": "job_1", "status": "PENDING" },
+                { "jobId": "job_2", "status": "PENDING" },
+            ],
+        } })))
+        .mount(&server)
+        .await;
+    for id in ["job_1", "job_2"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/api/jobs/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+                "jobId": id,
+                "status": "VISION_COMPLETED",
+            } })))
+            .mount(&server)
+            .await;
+    }
+    let finished = FinishedJobs::default();
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .metrics(finished.clone())
+        .build()
+        .unwrap();
+
+    let clip = |url: &str| BatchClip {
+        source_url: url.to_string(),
+        file_name: None,
+        download_token: None,
+        provider: None,
+    };
+    let results = client
+        .process_batch(BatchOptions {
+            clips: vec![
+                clip("https://example.com/a.mp4"),
+                clip("https://example.com/b.mp4"),
+            ],
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        *finished.0.lock().unwrap(),
+        ["VISION_COMPLETED", "VISION_COMPLETED"]
+    );
+}
+
+#[tokio::test]
+async fn strict_parsing_rejects_malformed_scenes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_1",
+            "status": "VISION_COMPLETED",
+            "processedData": { "scenes": [{ "summary": "renamed field", "endTs": 3.0 }] },
+        } })))
+        .mount(&server)
+        .await;
+    let build = |strict| {
+        ClientBuilder::new()
+            .api_key("fq_test_key")
+            .base_url(server.uri())
+            .strict_parsing(strict)
+            .build()
+            .unwrap()
+    };
+    let opts = ProcessOptions::default();
+
+    let lenient = build(false).wait_for_job("job_1", &opts).await.unwrap();
+    assert!(lenient.scenes.is_empty());
+
+    let strict = build(true);
+    let job = strict.get_job("job_1").await.unwrap();
+    assert!(job.result().unwrap().scenes.is_empty());
+    assert!(matches!(
+        strict.job_result(&job),
+        Err(FrameQueryError::Decode { .. })
+    ));
+
+    let err = build(true).wait_for_job("job_1", &opts).await.unwrap_err();
+    match err {
+        FrameQueryError::Decode {
+            ref message,
+            ref body_snippet,
+        } => {
+            assert!(message.contains("processedData.scenes"), "{message}");
+            assert!(body_snippet.contains("renamed field"), "{body_snippet}");
+        }
+        other => panic!("expected Decode, got {other:?}"),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn panicking_progress_callback_does_not_stop_polling() {
+    let (client, calls) = client(3);
+    let opts = ProcessOptions {
+        on_progress: Some(Arc::new(|_: &framequery::Job| panic!("progress bar bug"))),
+        ..opts(5, 60)
+    };
+
+    let result = client.wait_for_job("job_1", &opts).await.unwrap();
+
+    assert_eq!(result.job_id, "job_1");
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_source_fetch_is_resubmitted() {
+    let submits = Arc::new(AtomicU32::new(0));
+    let counter = submits.clone();
+    let client = Client::with_transport(
+        move |method: &str, path: &str, _: Option<&serde_json::Value>| {
+            if method == "POST" {
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                return Ok(json!({ "data": { "jobId": format!("job_{n}"), "status": "PENDING" } }));
+            }
+            let status = if path.ends_with("job_1") {
+                "FAILED_FETCH"
+            } else {
+                "VISION_COMPLETED"
+            };
+            Ok(json!({ "data": { "jobId": path.rsplit('/').next(), "status": status } }))
+        },
+    );
+    let opts = ProcessOptions {
+        retry_source_fetch: 2,
+        ..opts(5, 60)
+    };
+
+    let result = client
+        .process_url("https://cdn.example.com/new.mp4", Some(opts))
+        .await
+        .unwrap();
+
+    assert_eq!(result.job_id, "job_2");
+    assert_eq!(submits.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,261 @@
+use std::time::Duration;
+
+use framequery::{ClientBuilder, FrameQueryError};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client(server: &MockServer, max_retries: u32) -> framequery::Client {
+    ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .max_retries(max_retries)
+        .max_backoff(Duration::ZERO)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn max_retries_zero_sends_exactly_one_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, 0).await.get_quota().await.unwrap_err();
+    assert!(matches!(
+        err,
+        FrameQueryError::Api {
+            status_code: 503,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn max_retries_two_sends_at_most_three_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, 2).await.get_quota().await.unwrap_err();
+    assert!(matches!(
+        err,
+        FrameQueryError::Api {
+            status_code: 503,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn non_retryable_status_is_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, 2).await.get_quota().await.unwrap_err();
+    assert!(matches!(err, FrameQueryError::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn outrageous_retry_after_is_returned_not_slept_on() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .set_body_json(serde_json::json!({ "error": "slow down", "retryAfter": 86400 })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    let err = tokio::time::timeout(Duration::from_secs(5), client.get_quota())
+        .await
+        .expect("client slept on retryAfter")
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        FrameQueryError::RateLimit { retry_after: Some(secs), .. } if secs == 86400.0
+    ));
+}
+
+#[tokio::test]
+async fn small_retry_after_is_honored() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(
+            ResponseTemplate::new(429).set_body_json(serde_json::json!({ "retryAfter": 0.05 })),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "currentPlan": "pro",
+                "includedHours": 10.0,
+                "creditsBalanceHours": 0.0,
+                "resetDate": null
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .max_retries(1)
+        .build()
+        .unwrap();
+
+    let quota = client.get_quota().await.unwrap();
+    assert_eq!(quota.plan, "pro");
+}
+
+#[tokio::test]
+async fn request_timeout_is_retried_by_default() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(408))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, 1).await.get_quota().await.unwrap_err();
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn retry_on_extends_retryable_statuses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(425))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .max_retries(2)
+        .max_backoff(Duration::ZERO)
+        .retry_on(&[425])
+        .build()
+        .unwrap();
+    let err = client.get_quota().await.unwrap_err();
+    assert!(matches!(
+        err,
+        FrameQueryError::Api {
+            status_code: 425,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn exhausted_rate_limit_keeps_latest_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(
+            ResponseTemplate::new(429).set_body_json(serde_json::json!({ "retryAfter": 0.05 })),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .max_retries(1)
+        .build()
+        .unwrap();
+
+    let err = client.get_quota().await.unwrap_err();
+    assert!(matches!(err, FrameQueryError::RateLimit { .. }));
+    assert_eq!(err.retry_after(), Some(Duration::from_millis(50)));
+}
+
+#[tokio::test]
+async fn control_plane_timeout_applies_only_to_posts() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/job_1/cancel"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({}))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/quota"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "data": { "currentPlan": "pro", "includedHours": 10.0, "creditsBalanceHours": 0.0 }
+                }))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .max_retries(0)
+        .control_plane_timeout(Some(Duration::from_millis(100)))
+        .build()
+        .unwrap();
+
+    let err = client.cancel_job("job_1").await.unwrap_err();
+    assert!(err.is_timeout(), "{err:?}");
+    client.get_quota().await.unwrap();
+}
+
+#[tokio::test]
+async fn connection_failures_are_retryable() {
+    // Bind then drop a listener so the port refuses connections.
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(format!("http://{addr}"))
+        .max_retries(1)
+        .max_backoff(Duration::ZERO)
+        .build()
+        .unwrap();
+
+    let err = client.get_quota().await.unwrap_err();
+    assert!(matches!(err, FrameQueryError::Http(ref e) if e.is_connect()));
+    assert!(err.is_retryable());
+}
@@ -0,0 +1,776 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{client, video_file};
+use framequery::{FrameQueryError, ProcessOptions};
+use futures::StreamExt;
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, header, header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_create_job(server: &MockServer, job_id: &str, upload_path: &str) {
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "jobId": job_id,
+                "uploadUrl": format!("{}{upload_path}", server.uri()),
+                "expiresInSeconds": 3600,
+            }
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn rejected_signed_url_recreates_job() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_stale", "/upload/stale").await;
+    mount_create_job(&server, "job_fresh", "/upload/fresh").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/stale"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/fresh"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/job_stale/cancel"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("recreate");
+    let job = client(&server).upload(&file, None).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(job.id, "job_fresh");
+}
+
+#[tokio::test]
+async fn server_error_retries_same_signed_url() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("retry");
+    let job = client(&server).upload(&file, None).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(job.id, "job_1");
+}
+
+#[tokio::test]
+async fn client_error_from_blob_store_is_not_retried() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/job_1/cancel"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("no-retry");
+    let err = client(&server).upload(&file, None).await.unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+
+    assert!(matches!(
+        err,
+        FrameQueryError::Api {
+            status_code: 400,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn post_upload_method_sends_multipart_form() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "jobId": "job_1",
+                "uploadUrl": format!("{}/upload/form", server.uri()),
+                "uploadMethod": "POST",
+                "uploadFields": { "policy": "signed-policy" },
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/upload/form"))
+        .and(header_regex("content-type", "^multipart/form-data"))
+        .and(body_string_contains("signed-policy"))
+        .and(body_string_contains("not really a video"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("multipart");
+    let job = client(&server).upload(&file, None).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(job.id, "job_1");
+}
+
+#[tokio::test]
+async fn non_media_file_is_rejected_before_creating_a_job() {
+    let server = MockServer::start().await;
+    let file = std::env::temp_dir().join(format!("fq-{}-notes.mp4", std::process::id()));
+    std::fs::write(&file, b"meeting notes, not a video").unwrap();
+
+    let err = client(&server).upload(&file, None).await.unwrap_err();
+    assert!(
+        matches!(err, FrameQueryError::UnsupportedMedia { ref file_name, detected: None } if file_name.ends_with("notes.mp4")),
+        "{err:?}"
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+
+    // Opting out uploads it anyway.
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let opts = ProcessOptions {
+        skip_media_check: true,
+        ..Default::default()
+    };
+    let job = client(&server).upload(&file, Some(&opts)).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+    assert_eq!(job.id, "job_1");
+}
+
+#[tokio::test]
+async fn idempotent_upload_sends_its_key_and_keeps_the_job_on_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .and(body_string_contains("\"idempotencyKey\":\"order-42\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "jobId": "job_1", "uploadUrl": format!("{}/upload/1", server.uri()) }
+        })))
+        .expect(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/job_1/cancel"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let file = video_file("idempotent");
+    let opts = ProcessOptions {
+        idempotency_key: Some("order-42".into()),
+        ..Default::default()
+    };
+    let err = client(&server)
+        .upload(&file, Some(&opts))
+        .await
+        .unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+
+    assert!(
+        matches!(
+            err,
+            FrameQueryError::Api {
+                status_code: 403,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn job_id_is_reported_before_polling_so_a_failed_process_can_resume() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let created = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = created.clone();
+    let opts = ProcessOptions {
+        on_job_created: Some(std::sync::Arc::new(move |id: &str| {
+            seen.lock().unwrap().push(id.to_string());
+        })),
+        ..Default::default()
+    };
+    let file = video_file("resume");
+    let err = client(&server)
+        .process(&file, Some(opts))
+        .await
+        .unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+
+    assert!(err.is_retryable(), "{err:?}");
+    assert_eq!(*created.lock().unwrap(), ["job_1"]);
+}
+
+#[tokio::test]
+async fn invalid_upload_url_is_reported_with_the_job_id() {
+    for upload_url in ["", "/upload/1", "http://storage.example.com/upload/1"] {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/api/jobs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "jobId": "job_1", "uploadUrl": upload_url }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let file = video_file("bad-url");
+        let err = client(&server).upload(&file, None).await.unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+
+        match err {
+            FrameQueryError::Api {
+                status_code: 0,
+                ref message,
+                ..
+            } => assert!(
+                message.contains("invalid upload URL for job job_1"),
+                "{message}"
+            ),
+            other => panic!("expected Api, got {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn upload_stream_sends_chunks_with_the_given_length() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .and(header("content-length", "20"))
+        .and(body_string_contains("chunk one, chunk two"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let chunks = ["chunk one,", " chunk two"]
+        .map(|c| Ok::<_, std::io::Error>(bytes::Bytes::from_static(c.as_bytes())));
+    let job = client(&server)
+        .upload_stream("clip.mp4", futures::stream::iter(chunks), 20, None)
+        .await
+        .unwrap();
+
+    assert_eq!(job.id, "job_1");
+    assert_eq!(job.filename, "clip.mp4");
+    assert_eq!(job.status, "PENDING_UPLOAD");
+}
+
+#[tokio::test]
+async fn requested_checksum_is_sent_and_returned() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "jobId": "job_1",
+                "uploadUrl": format!("{}/upload/1", server.uri()),
+                "checksumAlgorithm": "SHA256",
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let digest = "S/ORTD1Qir3eCWjTtHgPFE7SYb8dJuFAU1S8D9mmhA8=";
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .and(header("x-amz-checksum-sha256", digest))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("checksum");
+    let job = client(&server).upload(&file, None).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(job.upload_checksum.as_deref(), Some(digest));
+}
+
+#[tokio::test]
+async fn requested_checksum_is_a_form_field_on_post_uploads() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "jobId": "job_1",
+                "uploadUrl": format!("{}/upload/form", server.uri()),
+                "uploadMethod": "POST",
+                "uploadFields": { "policy": "signed-policy" },
+                "checksumAlgorithm": "SHA256",
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let digest = "S/ORTD1Qir3eCWjTtHgPFE7SYb8dJuFAU1S8D9mmhA8=";
+    Mock::given(method("POST"))
+        .and(path("/upload/form"))
+        .and(body_string_contains(
+            "name=\"x-amz-checksum-sha256\"\r\n\r\nS/ORTD1Qir3eCWjTtHgPFE7SYb8dJuFAU1S8D9mmhA8=",
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("checksum-form");
+    let job = client(&server).upload(&file, None).await.unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(job.upload_checksum.as_deref(), Some(digest));
+}
+
+#[tokio::test]
+async fn streaming_transcript_yields_each_segment_once() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    for (status, segments) in [
+        ("PROCESSING", 1),
+        ("PROCESSING", 2),
+        ("VISION_COMPLETED", 3),
+    ] {
+        let transcript: Vec<_> = (0..segments)
+            .map(|i| json!({ "StartTime": i, "EndTime": i + 1, "Text": format!("s{i}") }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/v1/api/jobs/job_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+                "jobId": "job_1",
+                "status": status,
+                "processedData": { "transcript": transcript },
+            } })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let file = video_file("streaming");
+    let opts = ProcessOptions {
+        poll_interval: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let client = client(&server);
+    let texts: Vec<String> = client
+        .process_streaming_transcript(&file, Some(opts))
+        .map(|seg| seg.unwrap().text)
+        .collect()
+        .await;
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(texts, ["s0", "s1", "s2"]);
+}
+
+#[tokio::test]
+async fn strict_parsing_ends_the_transcript_stream_on_a_malformed_transcript() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_1",
+            "status": "PROCESSING",
+            "processedData": { "transcript": [{ "start": 0, "text": "renamed fields" }] },
+        } })))
+        .mount(&server)
+        .await;
+
+    let file = video_file("streaming-strict");
+    let client = framequery::ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .strict_parsing(true)
+        .build()
+        .unwrap();
+    let items: Vec<_> = client
+        .process_streaming_transcript(&file, None)
+        .collect()
+        .await;
+    std::fs::remove_file(&file).unwrap();
+
+    assert!(
+        matches!(items[..], [Err(FrameQueryError::Decode { .. })]),
+        "{items:?}"
+    );
+}
+
+#[tokio::test]
+async fn event_stream_reports_upload_progress_as_chunks_go_out() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .and(header("content-length", "300024"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_1",
+            "status": "VISION_COMPLETED",
+        } })))
+        .mount(&server)
+        .await;
+
+    let file = std::env::temp_dir().join(format!("fq-{}-large.mp4", std::process::id()));
+    let mut bytes = b"\0\0\0\x18ftypisom".to_vec();
+    bytes.resize(300_024, 0);
+    std::fs::write(&file, &bytes).unwrap();
+    let client = client(&server);
+    let sent: Vec<u64> = client
+        .process_with_events(&file, None)
+        .filter_map(|event| async move {
+            match event {
+                framequery::ProcessEvent::UploadProgress { sent, total } => {
+                    assert_eq!(total, 300_024);
+                    Some(sent)
+                }
+                _ => None,
+            }
+        })
+        .collect()
+        .await;
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(sent.first(), Some(&0));
+    assert_eq!(sent.last(), Some(&300_024));
+    assert!(sent.len() > 2, "{sent:?}");
+    assert!(sent.windows(2).all(|w| w[0] < w[1]), "{sent:?}");
+}
+
+#[tokio::test]
+async fn total_timeout_ends_the_event_stream_during_upload() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(10)))
+        .mount(&server)
+        .await;
+
+    let file = video_file("events-total-timeout");
+    let opts = ProcessOptions {
+        total_timeout: Some(Duration::from_millis(300)),
+        ..Default::default()
+    };
+    let started = std::time::Instant::now();
+    let client = client(&server);
+    let last = client
+        .process_with_events(&file, Some(opts))
+        .collect::<Vec<_>>()
+        .await
+        .pop();
+    std::fs::remove_file(&file).unwrap();
+
+    match last {
+        Some(framequery::ProcessEvent::Failed(message)) => {
+            assert!(message.contains("timed out"), "{message}")
+        }
+        other => panic!("expected Failed, got {other:?}"),
+    }
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn shutdown_ends_the_event_stream_and_reports_its_job() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_1",
+            "status": "PROCESSING",
+        } })))
+        .mount(&server)
+        .await;
+
+    let file = video_file("events-shutdown");
+    let client = client(&server);
+    let events = client.process_with_events(&file, None);
+    futures::pin_mut!(events);
+    loop {
+        if let Some(framequery::ProcessEvent::JobStatus(_)) = events.next().await {
+            break;
+        }
+    }
+
+    assert_eq!(client.shutdown(), ["job_1"]);
+    match events.next().await {
+        Some(framequery::ProcessEvent::Failed(message)) => {
+            assert!(message.contains("shutdown"), "{message}")
+        }
+        other => panic!("expected Failed, got {other:?}"),
+    }
+    assert!(events.next().await.is_none());
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[tokio::test]
+async fn submitted_url_job_keeps_the_creation_response_as_raw() {
+    let server = MockServer::start().await;
+    let data = json!({ "jobId": "job_1", "status": "PENDING_FETCH", "estimatedCompletionTimeSeconds": 90 });
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs/from-url"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": data })))
+        .mount(&server)
+        .await;
+    let url = "https://example.com/media/talk.mp4";
+
+    let job = client(&server).submit_url_job(url, None).await.unwrap();
+    assert_eq!(job.raw, data);
+    assert_eq!(job.filename, "talk.mp4");
+    assert_eq!(job.eta_seconds, Some(90.0));
+
+    let job = framequery::ClientBuilder::new()
+        .api_key("fq_test_key")
+        .base_url(server.uri())
+        .retain_raw(false)
+        .build()
+        .unwrap()
+        .submit_url_job(url, None)
+        .await
+        .unwrap();
+    assert!(job.raw.is_null());
+    assert_eq!(job.id, "job_1");
+}
+
+#[tokio::test]
+async fn client_deadline_covers_the_signed_url_upload() {
+    let server = MockServer::start().await;
+    mount_create_job(&server, "job_1", "/upload/1").await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(10)))
+        .mount(&server)
+        .await;
+
+    let file = video_file("deadline");
+    let started = std::time::Instant::now();
+    let err = client(&server)
+        .with_deadline(started + Duration::from_millis(300))
+        .upload(&file, None)
+        .await
+        .unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+
+    assert!(matches!(err, FrameQueryError::Timeout(_)), "{err:?}");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn artifact_from_another_origin_gets_no_key_and_is_retried() {
+    let api = MockServer::start().await;
+    let storage = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "jobId": "job_1",
+            "status": "VISION_COMPLETED",
+            "artifacts": { "report": format!("{}/reports/1.pdf", storage.uri()) },
+        } })))
+        .mount(&api)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/reports/1.pdf"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&storage)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/reports/1.pdf"))
+        .and(|req: &wiremock::Request| !req.headers.contains_key("authorization"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("%PDF"))
+        .expect(1)
+        .mount(&storage)
+        .await;
+
+    let bytes = client(&api)
+        .download_artifact("job_1", framequery::ArtifactKind::Report)
+        .await
+        .unwrap();
+    assert_eq!(&bytes[..], b"%PDF");
+}
+
+#[tokio::test]
+async fn upload_as_sends_the_given_file_name() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .and(body_string_contains("\"fileName\":\"Board meeting.mp4\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "jobId": "job_1", "uploadUrl": format!("{}/upload/1", server.uri()) }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("3f9a1c");
+    let job = client(&server)
+        .upload_as(&file, "Board meeting.mp4", None)
+        .await
+        .unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(job.filename, "Board meeting.mp4");
+}
+
+#[tokio::test]
+async fn oversized_file_is_reported_with_its_size() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/api/jobs"))
+        .respond_with(
+            ResponseTemplate::new(413)
+                .set_body_json(json!({ "error": "file exceeds plan limit", "maxFileSize": 10 })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = video_file("too-large");
+    let err = client(&server).upload(&file, None).await.unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+
+    assert!(matches!(
+        err,
+        FrameQueryError::FileTooLarge {
+            size: Some(30),
+            limit: Some(10),
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "file too large (30 bytes, limit 10): file exceeds plan limit"
+    );
+}
+
+#[tokio::test]
+async fn result_body_is_streamed_to_the_writer_verbatim() {
+    let server = MockServer::start().await;
+    let body = r#"{"data":{"jobId":"job_1","status":"VISION_COMPLETED"}}"#;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut out = Vec::new();
+    let written = client(&server)
+        .download_result_to("job_1", &mut out)
+        .await
+        .unwrap();
+
+    assert_eq!(written, body.len() as u64);
+    assert_eq!(out, body.as_bytes());
+}
+
+#[tokio::test]
+async fn result_download_retries_server_errors() {
+    let server = MockServer::start().await;
+    let body = r#"{"data":{"jobId":"job_1","status":"VISION_COMPLETED"}}"#;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/api/jobs/job_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut out = Vec::new();
+    client(&server)
+        .download_result_to("job_1", &mut out)
+        .await
+        .unwrap();
+
+    assert_eq!(out, body.as_bytes());
+}